@@ -1,9 +1,10 @@
-use anyhow::anyhow;
-use generator::run;
-use std::env;
+use anyhow::Context;
+use generator::check_messages;
+use std::fs;
 
 fn main() -> Result<(), anyhow::Error> {
-    let out_dir = env::var_os("OUT_DIR").ok_or_else(|| anyhow!("Couldn't get OUT_DIR"))?;
+    println!("cargo:rerun-if-changed=msg.lrc");
 
-    run(&out_dir)
+    let messages = fs::read_to_string("msg.lrc").context("Failed to read msg.lrc")?;
+    check_messages(&messages).context("msg.lrc")
 }
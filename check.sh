@@ -6,6 +6,8 @@ cargo build --target wasm32-unknown-unknown --release
 cargo clippy --target wasm32-unknown-unknown --release --no-default-features
 cargo clippy --target wasm32-unknown-unknown --release
 cargo build --bin copy-and-link
+cargo build --features cli --bin roll
+cargo clippy --features cli --bin roll
 for width in 500 400 300 200 150 130 110
 do
   cargo +nightly fmt --all -- --config max_width=$width
@@ -0,0 +1,180 @@
+//! The reader of the data files, the generator includes this file as well.
+
+use core::fmt::{self, Display, Formatter};
+use std::borrow::Cow;
+
+/// A data file: the header and the rows, without comments and empty lines.
+pub(crate) struct Csv<'a> {
+    pub(crate) header: Row<'a>,
+    pub(crate) rows: Vec<Row<'a>>,
+}
+
+/// A line of a data file, all positions start with 1.
+pub(crate) struct Row<'a> {
+    file: &'static str,
+    line: usize,
+    /// the column where each field starts and the (unquoted) field
+    fields: Vec<(usize, Cow<'a, str>)>,
+    /// the column after the last field
+    end: usize,
+}
+
+/// An error in a data file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvError {
+    pub file: &'static str,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for CsvError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}",
+            self.file, self.line, self.column, self.message
+        )
+    }
+}
+
+impl core::error::Error for CsvError {}
+
+impl<'a> Csv<'a> {
+    /// Parses a data file, lines starting with `#` are comments.
+    ///
+    /// Fields may be quoted (`"a, b"`), a quote inside a quoted field is written as `""`.
+    /// Lines with only empty fields (e.g. `,,,` from a spreadsheet) are skipped.
+    pub(crate) fn parse(file: &'static str, content: &'a str) -> Result<Self, CsvError> {
+        let mut rows = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.starts_with('#'))
+            .map(|(index, line)| Row::parse(file, index + 1, line))
+            .filter(|row| {
+                row.as_ref().map_or(true, |row| {
+                    row.fields.iter().any(|(_, field)| !field.is_empty())
+                })
+            });
+        let header = rows.next().ok_or_else(|| CsvError {
+            file,
+            line: 1,
+            column: 1,
+            message: "missing header".to_string(),
+        })??;
+        let rows = rows.collect::<Result<Vec<_>, _>>()?;
+        Ok(Csv { header, rows })
+    }
+}
+
+impl<'a> Row<'a> {
+    fn parse(file: &'static str, line: usize, mut rest: &'a str) -> Result<Self, CsvError> {
+        let mut row = Row {
+            file,
+            line,
+            fields: Vec::new(),
+            end: 1,
+        };
+        loop {
+            let column = row.end;
+            let (field, length, next) = if let Some(quoted) = rest.strip_prefix('"') {
+                let mut escaped = false;
+                let mut chars = quoted.char_indices();
+                let end = loop {
+                    match chars.next() {
+                        None => return Err(row.error_at(column, "unterminated quoted field")),
+                        Some((index, '"')) if quoted[index + 1..].starts_with('"') => {
+                            escaped = true;
+                            chars.next();
+                        }
+                        Some((index, '"')) => break index,
+                        Some(_) => {}
+                    }
+                };
+                let raw = &quoted[..end];
+                let field = if escaped {
+                    Cow::Owned(raw.replace("\"\"", "\""))
+                } else {
+                    Cow::Borrowed(raw)
+                };
+                let next = match &quoted[end + 1..] {
+                    "" => None,
+                    after => Some(after.strip_prefix(',').ok_or_else(|| {
+                        row.error_at(
+                            column + raw.chars().count() + 2,
+                            "expected `,` after a quoted field",
+                        )
+                    })?),
+                };
+                (field, raw.chars().count() + 2, next)
+            } else {
+                let (field, next) = match rest.split_once(',') {
+                    Some((field, next)) => (field, Some(next)),
+                    None => (rest, None),
+                };
+                if let Some(position) = field.find('"') {
+                    return Err(row.error_at(
+                        column + field[..position].chars().count(),
+                        "quote in an unquoted field",
+                    ));
+                }
+                (Cow::Borrowed(field), field.chars().count(), next)
+            };
+            row.fields.push((column, field));
+            row.end = column + length;
+            match next {
+                Some(next) => {
+                    rest = next;
+                    row.end += 1;
+                }
+                None => return Ok(row),
+            }
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// The field at `index`, an empty string if the row is shorter.
+    pub(crate) fn field(&self, index: usize) -> &str {
+        self.fields.get(index).map_or("", |(_, field)| field)
+    }
+
+    /// Fails if the row has less than `min` or more than `max` fields.
+    pub(crate) fn check_len(&self, min: usize, max: Option<usize>) -> Result<(), CsvError> {
+        if self.len() < min {
+            return Err(self.error(
+                self.len(),
+                format!("expected at least {min} fields, found {}", self.len()),
+            ));
+        }
+        if let Some(max) = max
+            && self.len() > max
+        {
+            return Err(self.error(
+                max,
+                format!("expected at most {max} fields, found {}", self.len()),
+            ));
+        }
+        Ok(())
+    }
+
+    /// An error at the field `index` (or after the end of the row).
+    pub(crate) fn error(&self, index: usize, message: impl Display) -> CsvError {
+        let column = self
+            .fields
+            .get(index)
+            .map_or(self.end, |(column, _)| *column);
+        self.error_at(column, message)
+    }
+
+    fn error_at(&self, column: usize, message: impl Display) -> CsvError {
+        CsvError {
+            file: self.file,
+            line: self.line,
+            column,
+            message: message.to_string(),
+        }
+    }
+}
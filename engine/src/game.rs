@@ -0,0 +1,205 @@
+pub use crate::game::generated::{Content, GameLanguage, Monster, SETUPS};
+use crate::homebrew::{Homebrew, HomebrewId};
+pub use crate::slot::{Color, Level, Number};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashSet;
+
+impl Monster {
+    /// The monster which miniature is used (itself if it has an own one).
+    #[must_use]
+    pub fn sculpt(self) -> Monster {
+        self.miniature().unwrap_or(self)
+    }
+}
+
+/// A monster of the game or a homebrew one.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AnyMonster {
+    Game(Monster),
+    Homebrew(HomebrewId),
+}
+
+impl From<Monster> for AnyMonster {
+    fn from(monster: Monster) -> Self {
+        AnyMonster::Game(monster)
+    }
+}
+
+impl AnyMonster {
+    /// All monsters of the game and the homebrew ones.
+    pub fn iter(homebrew: &[Homebrew]) -> impl Iterator<Item = AnyMonster> {
+        Monster::iter()
+            .map(AnyMonster::Game)
+            .chain(homebrew.iter().map(|h| AnyMonster::Homebrew(h.id)))
+    }
+
+    /// The definition of a homebrew monster, `None` for one of the game or if it no longer exists.
+    pub fn homebrew(self, homebrew: &[Homebrew]) -> Option<&Homebrew> {
+        match self {
+            AnyMonster::Game(_) => None,
+            AnyMonster::Homebrew(id) => homebrew.iter().find(|h| h.id == id),
+        }
+    }
+
+    /// The color, `None` if it's a homebrew monster which no longer exists.
+    pub fn color(self, homebrew: &[Homebrew]) -> Option<Color> {
+        match self {
+            AnyMonster::Game(monster) => Some(monster.color()),
+            AnyMonster::Homebrew(_) => self.homebrew(homebrew).map(|h| h.color),
+        }
+    }
+
+    /// The monster which miniature is used (itself if it has an own one).
+    #[must_use]
+    pub fn sculpt(self, homebrew: &[Homebrew]) -> AnyMonster {
+        match self {
+            AnyMonster::Game(monster) => AnyMonster::Game(monster.sculpt()),
+            AnyMonster::Homebrew(_) => self
+                .homebrew(homebrew)
+                .and_then(|h| h.miniature)
+                .map_or(self, |monster| AnyMonster::Game(monster.sculpt())),
+        }
+    }
+
+    /// The name in the language, `?` if it's a homebrew monster which no longer exists.
+    pub fn name(self, game_language: GameLanguage, homebrew: &[Homebrew]) -> &str {
+        match self {
+            AnyMonster::Game(monster) => monster.name(game_language),
+            AnyMonster::Homebrew(_) => self
+                .homebrew(homebrew)
+                .map_or("?", |h| h.name(game_language)),
+        }
+    }
+}
+
+/// A stored monster, the name of a monster of the game or the id of a homebrew one.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredMonster {
+    Game(String),
+    Homebrew(HomebrewId),
+}
+
+impl StoredMonster {
+    fn resolve(self) -> Option<AnyMonster> {
+        match self {
+            StoredMonster::Game(name) => Monster::from_str(&name).map(AnyMonster::Game),
+            StoredMonster::Homebrew(id) => Some(AnyMonster::Homebrew(id)),
+        }
+    }
+}
+
+/// Deserializes a set of monsters, monsters of the game which no longer exist are dropped.
+pub fn deserialize_monster_set<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<HashSet<AnyMonster>, D::Error> {
+    Ok(Vec::<StoredMonster>::deserialize(deserializer)?
+        .into_iter()
+        .filter_map(StoredMonster::resolve)
+        .collect())
+}
+
+/// Deserializes a count per monster, monsters of the game which no longer exist are dropped.
+pub fn deserialize_monster_counts<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<(AnyMonster, usize)>, D::Error> {
+    Ok(Vec::<(StoredMonster, usize)>::deserialize(deserializer)?
+        .into_iter()
+        .filter_map(|(monster, count)| Some((monster.resolve()?, count)))
+        .collect())
+}
+
+/// Deserializes an optional monster, a monster of the game which no longer exists becomes `None`.
+pub fn deserialize_any_monster<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<AnyMonster>, D::Error> {
+    Ok(Option::<StoredMonster>::deserialize(deserializer)?.and_then(StoredMonster::resolve))
+}
+
+/// Deserializes an optional monster, a monster which no longer exists becomes `None`.
+pub fn deserialize_monster<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Monster>, D::Error> {
+    Ok(Option::<String>::deserialize(deserializer)?.and_then(|name| Monster::from_str(&name)))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct Chapter(pub usize);
+
+impl Color {
+    pub fn name(self, game_language: GameLanguage) -> &'static str {
+        match self {
+            Color::White => game_language.color_white_name(),
+            Color::Gray => game_language.color_gray_name(),
+            Color::Black => game_language.color_black_name(),
+            Color::Commander => game_language.color_commander_name(),
+            Color::Special => panic!("called Color::Special::short()"),
+            Color::SpecialCommander => panic!("called Color::SpecialCommander::short()"),
+        }
+    }
+
+    pub fn short(self, game_language: GameLanguage) -> &'static str {
+        match self {
+            Color::White => game_language.color_white_short(),
+            Color::Gray => game_language.color_gray_short(),
+            Color::Black => game_language.color_black_short(),
+            Color::Commander => game_language.color_commander_short(),
+            Color::Special => panic!("called Color::Special::short()"),
+            Color::SpecialCommander => panic!("called Color::SpecialCommander::short()"),
+        }
+    }
+
+    pub fn size(self, game_language: GameLanguage) -> Option<&'static str> {
+        match self {
+            Color::White | Color::Gray => Some(game_language.size_small()),
+            Color::Black => Some(game_language.size_big()),
+            Color::Commander | Color::Special | Color::SpecialCommander => None,
+        }
+    }
+
+    pub fn prefix(self, game_language: GameLanguage) -> &'static str {
+        match self {
+            Color::White => game_language.color_white_prefix(),
+            Color::Gray => game_language.color_gray_prefix(),
+            Color::Black => game_language.color_black_prefix(),
+            Color::Commander => game_language.color_commander_prefix(),
+            Color::Special => panic!("called Color::Special::prefix()"),
+            Color::SpecialCommander => panic!("called Color::SpecialCommander::prefix()"),
+        }
+    }
+
+    pub fn css_prefix(self) -> &'static str {
+        match self {
+            Color::White => "w",
+            Color::Gray => "g",
+            Color::Black => "b",
+            Color::Commander | Color::SpecialCommander => "c",
+            Color::Special => panic!("called Color::Special::prefix_lower()"),
+        }
+    }
+}
+
+impl Level {
+    pub fn name(self, game_language: GameLanguage) -> &'static str {
+        match self {
+            Level::Rookie => game_language.level_rookie(),
+            Level::Fighter => game_language.level_fighter(),
+            Level::Veteran => game_language.level_veteran(),
+            Level::Champion => game_language.level_champion(),
+        }
+    }
+}
+
+mod generated {
+    use crate::game::{Chapter, Color, Level, Number};
+    use crate::setup::{Setup, SetupItem, SetupKind};
+    use enum_tools::EnumTools;
+    use serde::{Deserialize, Serialize};
+    use typed_i18n::TypedI18N;
+
+    include!(concat!(env!("OUT_DIR"), "/generated_language.rs"));
+    include!(concat!(env!("OUT_DIR"), "/generated_content.rs"));
+    include!(concat!(env!("OUT_DIR"), "/generated_monster.rs"));
+    include!(concat!(env!("OUT_DIR"), "/generated_setup.rs"));
+}
@@ -0,0 +1,211 @@
+//! Random setups which follow the patterns of the ones of the game.
+
+use crate::game::{Chapter, Color, Level, Number};
+use crate::setup::{CustomSetup, SetupItem, SetupKind};
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+/// The threat of a commander, which has no level.
+pub const COMMANDER_THREAT: u32 = 8;
+
+/// The regular colors and how often they appear in the setups of the game.
+const COLORS: [(Color, u32); 3] = [(Color::White, 4), (Color::Gray, 5), (Color::Black, 2)];
+
+const LEVELS: [Level; 4] = [
+    Level::Rookie,
+    Level::Fighter,
+    Level::Veteran,
+    Level::Champion,
+];
+
+const NUMBERS: [Number; 5] = [
+    Number::One,
+    Number::Two,
+    Number::Three,
+    Number::Four,
+    Number::Five,
+];
+
+/// The numbers of the monsters next to a commander, which is `C1`.
+const ESCORTS: [Number; 2] = [Number::Three, Number::Five];
+
+/// The most monsters with the same number.
+const MAX_PER_NUMBER: usize = 2;
+
+/// How dangerous a monster of the color and level is.
+pub fn threat(color: Color, level: Level) -> u32 {
+    let color = match color {
+        Color::White => 1,
+        Color::Gray => 2,
+        Color::Black => 3,
+        Color::Commander | Color::Special | Color::SpecialCommander => return COMMANDER_THREAT,
+    };
+    let level = match level {
+        Level::Rookie => 0,
+        Level::Fighter => 1,
+        Level::Veteran => 2,
+        Level::Champion => 3,
+    };
+    color + level
+}
+
+/// The threat of the monsters of a setup which are placed for the number of players.
+pub fn setup_threat(items: &[SetupItem], players: Number) -> u32 {
+    items
+        .iter()
+        .filter(|item| !item.exclude && item.number <= players)
+        .map(|item| threat(item.color, item.level))
+        .sum()
+}
+
+/// The step of the last level, champion.
+const LAST_STEP: usize = 9;
+
+/// The levels of a step: the level rises every three steps and the steps in between have some
+/// monsters of the next level, e.g. step 1 has some fighters.
+///
+/// Returns the index of the level and the chance (in thirds) of a monster to be of the next level.
+fn step_level(step: usize) -> (usize, u32) {
+    match step / 3 {
+        index @ 0..3 => (index, u32::try_from(step % 3).unwrap_or_default()),
+        _ => (LEVELS.len() - 1, 0),
+    }
+}
+
+/// The number of numbers which are placed for the players.
+fn placed_numbers(players: Number) -> u32 {
+    let placed = NUMBERS.iter().filter(|number| **number <= players).count();
+    u32::try_from(placed).unwrap_or_default()
+}
+
+/// Generates a door: a random color and level for the monsters of each number.
+///
+/// The threat of the monsters which are placed for `players` is at most `threat` (unless it's too
+/// low for one monster per number), the numbers above get as much per number, resp. share it if
+/// none is placed. A number has no monster twice. If the threat is not enough, a monster of the
+/// next level gets the level of the chapter resp. a cheaper color.
+///
+/// A door with a `commander` has it alone as `C1` and only two other monsters, like the doors of
+/// the game.
+pub fn door<R: Rng + ?Sized>(
+    players: Number,
+    threat: u32,
+    chapter: Chapter,
+    commander: bool,
+    rng: &mut R,
+) -> Vec<SetupItem> {
+    let step = chapter.0.saturating_sub(1);
+    door_at_step(players, threat, step, commander, rng)
+}
+
+/// Generates a chapter: the setup and `doors` doors with the same `chapter`.
+///
+/// The levels rise from rookie in the setup to champion in the last door. The `threat` is the one
+/// of a part with rookies, a placed number gets one more per level, thus the number of monsters
+/// stays about the same. The last door has a commander if `commander`.
+pub fn adventure<R: Rng + ?Sized>(
+    players: Number,
+    threat: u32,
+    chapter: Chapter,
+    doors: u8,
+    commander: bool,
+    rng: &mut R,
+) -> Vec<CustomSetup> {
+    (0..=doors)
+        .map(|part| {
+            let step = (usize::from(part) * LAST_STEP)
+                .checked_div(usize::from(doors))
+                .unwrap_or_default();
+            let level = u32::try_from(step_level(step).0).unwrap_or_default();
+            let has_commander = commander && part == doors && part > 0;
+            let threat = threat
+                + placed_numbers(players) * level
+                + if has_commander { COMMANDER_THREAT } else { 0 };
+            CustomSetup {
+                chapter,
+                kind: if part == 0 {
+                    SetupKind::Setup
+                } else {
+                    SetupKind::Door(part)
+                },
+                labels: Vec::new(),
+                monsters: door_at_step(players, threat, step, has_commander, rng),
+            }
+        })
+        .collect()
+}
+
+fn door_at_step<R: Rng + ?Sized>(
+    players: Number,
+    threat: u32,
+    step: usize,
+    commander: bool,
+    rng: &mut R,
+) -> Vec<SetupItem> {
+    let (base, up_chance) = step_level(step);
+    let mut items = Vec::new();
+    let (numbers, threat) = if commander {
+        items.push(item(Number::One, Color::Commander, Level::Rookie));
+        (&ESCORTS[..], threat.saturating_sub(COMMANDER_THREAT))
+    } else {
+        (&NUMBERS[..], threat)
+    };
+
+    // the threat is shared by the placed numbers, resp. all if none is placed (the escorts of one
+    // player)
+    let placed = numbers.iter().filter(|number| **number <= players).count();
+    let shared =
+        u32::try_from(if placed == 0 { numbers.len() } else { placed }).unwrap_or_default();
+    let mut index = 0;
+    for number in numbers {
+        let share = if *number <= players {
+            index += 1;
+            threat / shared + u32::from(index <= threat % shared)
+        } else {
+            threat / shared
+        };
+        let mut left = share;
+        for slot in 0..MAX_PER_NUMBER {
+            let top = if base + 1 < LEVELS.len() && rng.random_ratio(up_chance, 3) {
+                base + 1
+            } else {
+                base
+            };
+            let fitting = COLORS
+                .iter()
+                .copied()
+                .filter_map(|(color, weight)| {
+                    LEVELS[base..=top]
+                        .iter()
+                        .rev()
+                        // the same monster only once per number
+                        .filter(|level| !items.contains(&item(*number, color, **level)))
+                        .find(|level| self::threat(color, **level) <= left)
+                        .map(|level| (color, *level, weight))
+                })
+                .collect::<Vec<_>>();
+            let Ok((color, level, _)) = fitting.choose_weighted(rng, |(_, _, weight)| *weight)
+            else {
+                if slot == 0 {
+                    // each number has at least one monster
+                    items.push(item(*number, Color::White, LEVELS[base]));
+                }
+                break;
+            };
+            items.push(item(*number, *color, *level));
+            left -= self::threat(*color, *level);
+        }
+    }
+    items
+}
+
+fn item(number: Number, color: Color, level: Level) -> SetupItem {
+    SetupItem {
+        number,
+        color,
+        level,
+        monster: None,
+        exclude: false,
+        random: None,
+    }
+}
@@ -0,0 +1,30 @@
+//! Monsters defined by the user, they are drawn like the ones of the game.
+
+use crate::game::{Color, GameLanguage, Monster};
+use serde::{Deserialize, Serialize};
+
+/// The id of a homebrew monster, it's kept when other ones are removed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct HomebrewId(pub u32);
+
+/// A monster defined by the user.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Homebrew {
+    pub id: HomebrewId,
+    /// the translated names, English first
+    pub names: Vec<(GameLanguage, String)>,
+    pub color: Color,
+    /// the monster of the game whose miniature is used, `None` if it has an own one
+    pub miniature: Option<Monster>,
+}
+
+impl Homebrew {
+    /// The name in the language, the English one if it's not translated.
+    pub fn name(&self, game_language: GameLanguage) -> &str {
+        self.names
+            .iter()
+            .find(|(language, _)| *language == game_language)
+            .or(self.names.first())
+            .map_or("", |(_, name)| name)
+    }
+}
@@ -0,0 +1,30 @@
+#![forbid(unsafe_code)]
+#![deny(unused_crate_dependencies)]
+#![warn(clippy::std_instead_of_core)]
+#![warn(clippy::pedantic)]
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::missing_panics_doc)]
+#![allow(clippy::module_name_repetitions)]
+#![allow(clippy::must_use_candidate)]
+#![allow(clippy::similar_names)]
+#![allow(clippy::too_many_lines)]
+#![allow(clippy::unsafe_derive_deserialize)]
+
+//! The monsters and setups of Drunagor and the random selection of monsters, without any UI.
+
+pub mod game;
+pub mod generate;
+pub mod homebrew;
+pub mod roll;
+pub mod setup;
+
+mod csv;
+mod setup_row;
+mod slot;
+
+/// The miniature images as (source, destination) file names.
+pub use crate::image::MONSTER_IMAGES;
+
+mod image {
+    include!(concat!(env!("OUT_DIR"), "/generated_monster_image.rs"));
+}
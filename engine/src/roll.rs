@@ -0,0 +1,526 @@
+use crate::game::{AnyMonster, Color, Content, Level, Number};
+use crate::homebrew::Homebrew;
+use crate::setup::SetupItem;
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+use core::iter::repeat_n;
+use rand::Rng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// How much more likely a favorite monster is drawn.
+pub const FAVORITE_WEIGHT: usize = 3;
+
+/// A monster of a rolled setup.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Item {
+    pub number: Number,
+    pub color: Color,
+    pub level: Level,
+    #[serde(deserialize_with = "crate::game::deserialize_any_monster")]
+    pub monster: Option<AnyMonster>,
+    pub preset: bool,
+    /// why no monster could be drawn
+    #[serde(default)]
+    pub missing: Option<Missing>,
+}
+
+/// Why no monster could be drawn for an item.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Missing {
+    /// the enabled content has too few monsters of the color
+    NotEnough,
+    /// too many monsters of the color are banned
+    Banned,
+    /// the remaining monsters share their miniature with other ones
+    Miniature,
+}
+
+impl Missing {
+    fn reason(
+        color: Color,
+        todo: &BTreeMap<Color, BTreeSet<Level>>,
+        enabled_monsters: &[AnyMonster],
+        settings: &RollSettings,
+    ) -> Self {
+        let needed = todo.get(&color).map_or(0, BTreeSet::len);
+        let enabled = enabled_monsters
+            .iter()
+            .filter(|monster| monster.color(&settings.homebrew) == Some(color))
+            .collect::<Vec<_>>();
+        if enabled.len() < needed {
+            Missing::NotEnough
+        } else if enabled
+            .iter()
+            .filter(|monster| !settings.banned.contains(monster))
+            .count()
+            < needed
+        {
+            Missing::Banned
+        } else {
+            Missing::Miniature
+        }
+    }
+}
+
+/// Everything which influences which monsters are drawn.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RollSettings {
+    /// the enabled content
+    pub content: HashSet<Content>,
+    /// monsters which are never drawn
+    pub banned: HashSet<AnyMonster>,
+    /// monsters which are drawn more often
+    pub favorites: HashSet<AnyMonster>,
+    /// monsters which are only drawn if there are not enough other ones
+    pub recent: HashSet<AnyMonster>,
+    /// random (color, level) picks which are already decided (e.g. locked ones), they are drawn
+    /// again if they are disabled, banned or their miniature is used by a preset monster
+    pub fixed: HashMap<(Color, Level), AnyMonster>,
+    /// the homebrew monsters, they are always enabled
+    pub homebrew: Vec<Homebrew>,
+    /// the owned copies of each sculpt, sculpts which are not listed are unlimited
+    pub copies: HashMap<AnyMonster, usize>,
+    /// only the items up to the number of players need a figure, `None` for all
+    pub players: Option<Number>,
+}
+
+impl RollSettings {
+    fn is_enabled(&self, monster: AnyMonster) -> bool {
+        match monster {
+            AnyMonster::Game(monster) => {
+                !monster.color().is_any_special() && self.content.contains(&monster.content())
+            }
+            AnyMonster::Homebrew(_) => monster
+                .color(&self.homebrew)
+                .is_some_and(|color| !color.is_any_special()),
+        }
+    }
+
+    /// Whether the item is placed, thus needs a figure.
+    fn is_placed(&self, number: Number) -> bool {
+        self.players.is_none_or(|players| number <= players)
+    }
+
+    /// Whether `needed` figures of the sculpt of the monster are owned.
+    fn has_copies(&self, monster: AnyMonster, needed: usize) -> bool {
+        self.copies
+            .get(&monster.sculpt(&self.homebrew))
+            .is_none_or(|owned| *owned >= needed)
+    }
+}
+
+/// A sculpt of which more figures are needed than owned.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Shortage {
+    pub sculpt: AnyMonster,
+    pub needed: usize,
+    pub owned: usize,
+}
+
+/// The sculpts of a rolled setup and how many figures of each are placed, in the order of the items.
+pub fn figures(settings: &RollSettings, output: &[Item]) -> Vec<(AnyMonster, usize)> {
+    let mut figures = Vec::<(AnyMonster, usize)>::new();
+    for item in output.iter().filter(|item| settings.is_placed(item.number)) {
+        let Some(monster) = item.monster else {
+            continue;
+        };
+        let sculpt = monster.sculpt(&settings.homebrew);
+        match figures.iter_mut().find(|(s, _)| *s == sculpt) {
+            Some((_, count)) => *count += 1,
+            None => figures.push((sculpt, 1)),
+        }
+    }
+    figures
+}
+
+/// The sculpts of a rolled setup of which not enough figures are owned, in the order of the items.
+pub fn shortages(settings: &RollSettings, output: &[Item]) -> Vec<Shortage> {
+    figures(settings, output)
+        .into_iter()
+        .filter_map(|(sculpt, needed)| {
+            let owned = *settings.copies.get(&sculpt)?;
+            (owned < needed).then_some(Shortage {
+                sculpt,
+                needed,
+                owned,
+            })
+        })
+        .collect()
+}
+
+/// Not all random monsters could be drawn, contains the items with the drawn ones.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Incomplete(pub Vec<Item>);
+
+impl Display for Incomplete {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let missing = self.0.iter().filter(|item| item.missing.is_some()).count();
+        write!(f, "{missing} monster(s) could not be drawn")
+    }
+}
+
+impl Error for Incomplete {}
+
+/// Draws the random monsters of a setup.
+///
+/// Excluded items are skipped, preset monsters are kept. Items with a random color or level
+/// get one first, see [`resolve`]. The same `rng` state always results in the same selection.
+pub fn roll<R: Rng + ?Sized>(
+    settings: &RollSettings,
+    selected: &[SetupItem],
+    rng: &mut R,
+) -> Result<Vec<Item>, Incomplete> {
+    // gather available monsters (by type)
+    let enabled_monsters = AnyMonster::iter(&settings.homebrew)
+        .filter(|monster| settings.is_enabled(*monster))
+        .collect::<Vec<_>>();
+    let mut avail_monsters = enabled_monsters
+        .iter()
+        .copied()
+        .filter(|monster| !settings.banned.contains(monster))
+        .collect::<Vec<_>>();
+
+    let selected = &resolve(settings, selected, &avail_monsters, rng);
+
+    // ordered, so that the same seed always results in the same selection
+    let mut todo = BTreeMap::<Color, BTreeSet<Level>>::new();
+    for item in selected {
+        if item.monster.is_none() {
+            todo.entry(item.color).or_default().insert(item.level);
+        }
+    }
+
+    // fixed picks are kept, their monsters are removed like the preset ones
+    let fixed = settings
+        .fixed
+        .iter()
+        .filter(|((co, le), _)| todo.get(co).is_some_and(|levels| levels.contains(le)))
+        .filter(|(_, monster)| settings.is_enabled(**monster))
+        .filter(|(_, monster)| !settings.banned.contains(*monster))
+        .filter(|(_, monster)| !is_preset(selected, **monster, &settings.homebrew))
+        .map(|(key, monster)| (*key, *monster))
+        .collect::<HashMap<_, _>>();
+    for (co, le) in fixed.keys() {
+        if let Some(levels) = todo.get_mut(co) {
+            levels.remove(le);
+        }
+    }
+    let sculpt = |monster: AnyMonster| monster.sculpt(&settings.homebrew);
+    avail_monsters.retain(|monster| !fixed.values().any(|m| sculpt(*m) == sculpt(*monster)));
+
+    // the figures the preset monsters and each random (color, level) need
+    let mut preset_figures = HashMap::<AnyMonster, usize>::new();
+    let mut random_figures = HashMap::<(Color, Level), usize>::new();
+    for item in selected
+        .iter()
+        .filter(|item| !item.exclude && settings.is_placed(item.number))
+    {
+        let count = match item.monster {
+            Some(monster) => preset_figures
+                .entry(sculpt(AnyMonster::Game(monster)))
+                .or_default(),
+            None => random_figures.entry((item.color, item.level)).or_default(),
+        };
+        *count += 1;
+    }
+    let fits = |monster: AnyMonster, key: (Color, Level)| {
+        let preset = preset_figures.get(&sculpt(monster)).copied();
+        let random = random_figures.get(&key).copied();
+        settings.has_copies(
+            monster,
+            preset.unwrap_or_default() + random.unwrap_or_default(),
+        )
+    };
+
+    let slots = todo.values().map(BTreeSet::len).sum::<usize>();
+    let mut drawn = select(
+        avail_monsters
+            .iter()
+            .copied()
+            .filter(|m| !is_preset(selected, *m, &settings.homebrew))
+            .collect(),
+        &todo,
+        settings,
+        &fits,
+        rng,
+    );
+    if drawn.len() < slots {
+        // allow monsters which share a miniature with a preset one
+        let relaxed = select(avail_monsters.clone(), &todo, settings, &fits, rng);
+        if relaxed.len() > drawn.len() {
+            drawn = relaxed;
+        }
+    }
+    if drawn.len() < slots && !settings.copies.is_empty() {
+        // allow monsters of which not enough figures are owned, see `shortages`
+        let unlimited = select(avail_monsters, &todo, settings, &|_, _| true, rng);
+        if unlimited.len() > drawn.len() {
+            drawn = unlimited;
+        }
+    }
+    let complete = drawn.len() == slots;
+    drawn.extend(fixed);
+
+    let mut output = Vec::new();
+    for item in selected {
+        if !item.exclude {
+            if item.monster.is_none() {
+                let monster = drawn.get(&(item.color, item.level)).copied();
+                output.push(Item {
+                    number: item.number,
+                    color: item.color,
+                    level: item.level,
+                    monster,
+                    preset: false,
+                    missing: monster
+                        .is_none()
+                        .then(|| Missing::reason(item.color, &todo, &enabled_monsters, settings)),
+                });
+            } else {
+                output.push(Item {
+                    number: item.number,
+                    color: item.color,
+                    level: item.level,
+                    monster: item.monster.map(AnyMonster::Game),
+                    preset: true,
+                    missing: None,
+                });
+            }
+        }
+    }
+
+    if complete {
+        Ok(output)
+    } else {
+        Err(Incomplete(output))
+    }
+}
+
+/// Draws the color and level of the items with a random one.
+///
+/// Items with the same random slot get the same color and level. A (color, level) of which the
+/// monster is already decided (e.g. locked) is preferred, otherwise one which is not used by
+/// another item and of which enough monsters are available. The `rng` is only used if there are
+/// such items.
+fn resolve<R: Rng + ?Sized>(
+    settings: &RollSettings,
+    selected: &[SetupItem],
+    avail_monsters: &[AnyMonster],
+    rng: &mut R,
+) -> Vec<SetupItem> {
+    let is_drawn = |item: &SetupItem| item.monster.is_none() && !item.exclude;
+    let mut used = selected
+        .iter()
+        .filter(|item| is_drawn(item) && item.random.is_none())
+        .map(|item| (item.color, item.level))
+        .collect::<BTreeSet<_>>();
+    let mut resolved = HashMap::new();
+    selected
+        .iter()
+        .map(|item| {
+            let Some(random) = item.random.filter(|_| is_drawn(item)) else {
+                return item.clone();
+            };
+            let (color, level) = *resolved
+                .entry((item.color, item.level, random))
+                .or_insert_with(|| {
+                    let choices = item.choices();
+                    let has_monsters = |color: Color| {
+                        avail_monsters
+                            .iter()
+                            .filter(|monster| monster.color(&settings.homebrew) == Some(color))
+                            .count()
+                            > used.iter().filter(|(co, _)| *co == color).count()
+                    };
+                    let fixed = choices
+                        .iter()
+                        .copied()
+                        .filter(|key| settings.fixed.contains_key(key) && !used.contains(key))
+                        .collect::<Vec<_>>();
+                    let free = choices
+                        .iter()
+                        .copied()
+                        .filter(|key| !used.contains(key) && has_monsters(key.0))
+                        .collect::<Vec<_>>();
+                    let choice = [fixed, free, choices]
+                        .into_iter()
+                        .find_map(|keys| keys.choose(rng).copied())
+                        .unwrap_or((item.color, item.level));
+                    used.insert(choice);
+                    choice
+                });
+            SetupItem {
+                color,
+                level,
+                ..item.clone()
+            }
+        })
+        .collect()
+}
+
+/// Draws another monster for one random (color, level) of a rolled setup.
+///
+/// The current monster and the miniatures used by the other items are not drawn, returns `None`
+/// if there is no other monster. Monsters which were not used recently are preferred.
+pub fn reroll<R: Rng + ?Sized>(
+    settings: &RollSettings,
+    selected: &[SetupItem],
+    output: &[Item],
+    color: Color,
+    level: Level,
+    rng: &mut R,
+) -> Option<AnyMonster> {
+    let mut current = None;
+    let mut used = HashSet::new();
+    let mut needed = 0;
+    for item in output {
+        if !item.preset && item.color == color && item.level == level {
+            current = current.or(item.monster);
+            if settings.is_placed(item.number) {
+                needed += 1;
+            }
+        } else if let Some(monster) = item.monster {
+            used.insert(monster.sculpt(&settings.homebrew));
+        }
+    }
+
+    let avail = |skip_preset: bool, check_copies: bool| {
+        AnyMonster::iter(&settings.homebrew)
+            .filter(|monster| monster.color(&settings.homebrew) == Some(color))
+            .filter(|monster| settings.is_enabled(*monster))
+            .filter(|monster| !settings.banned.contains(monster))
+            .filter(|monster| Some(*monster) != current)
+            .filter(|monster| !used.contains(&monster.sculpt(&settings.homebrew)))
+            .filter(|monster| !skip_preset || !is_preset(selected, *monster, &settings.homebrew))
+            .filter(|monster| !check_copies || settings.has_copies(*monster, needed))
+            .collect::<Vec<_>>()
+    };
+    let mut avail_monsters = avail(true, true);
+    if avail_monsters.is_empty() {
+        avail_monsters = avail(false, true);
+    }
+    if avail_monsters.is_empty() {
+        avail_monsters = avail(false, false);
+    }
+    if avail_monsters
+        .iter()
+        .any(|monster| !settings.recent.contains(monster))
+    {
+        avail_monsters.retain(|monster| !settings.recent.contains(monster));
+    }
+    avail_monsters
+        .choose_weighted(rng, |monster| {
+            if settings.favorites.contains(monster) {
+                FAVORITE_WEIGHT
+            } else {
+                1
+            }
+        })
+        .ok()
+        .copied()
+}
+
+/// Whether the monster (or its miniature) is already used by a preset or excluded.
+fn is_preset(selected: &[SetupItem], monster: AnyMonster, homebrew: &[Homebrew]) -> bool {
+    selected
+        .iter()
+        .filter_map(|item| item.monster)
+        .any(|m| AnyMonster::Game(m.sculpt()) == monster.sculpt(homebrew))
+}
+
+/// Assigns a monster to as many (color, level) as possible, no miniature is used twice.
+///
+/// A monster is only assigned to a (color, level) if enough figures of it are owned (`fits`).
+///
+/// The monsters are tried in a random order, if a monster is already taken it's checked
+/// whether the other (color, level) can switch to another one (augmenting paths), thus a
+/// complete assignment is always found if there is one.
+fn select<R: Rng + ?Sized>(
+    avail: Vec<AnyMonster>,
+    todo: &BTreeMap<Color, BTreeSet<Level>>,
+    settings: &RollSettings,
+    fits: &dyn Fn(AnyMonster, (Color, Level)) -> bool,
+    rng: &mut R,
+) -> HashMap<(Color, Level), AnyMonster> {
+    // favorites are added multiple times, thus they're more likely to be in front
+    let mut avail = avail
+        .into_iter()
+        .flat_map(|monster| {
+            let weight = if settings.favorites.contains(&monster) {
+                FAVORITE_WEIGHT
+            } else {
+                1
+            };
+            repeat_n(monster, weight)
+        })
+        .collect::<Vec<_>>();
+    avail.shuffle(rng);
+    let mut seen = HashSet::new();
+    avail.retain(|monster| seen.insert(*monster));
+    // prefer monsters which were not used recently
+    avail.sort_by_key(|monster| settings.recent.contains(monster));
+
+    let slots = todo
+        .iter()
+        .flat_map(|(co, levels)| levels.iter().map(|le| (*co, *le)))
+        .collect::<Vec<_>>();
+    let candidates = slots
+        .iter()
+        .map(|(co, le)| {
+            avail
+                .iter()
+                .copied()
+                .filter(|m| m.color(&settings.homebrew) == Some(*co))
+                .filter(|m| fits(*m, (*co, *le)))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut assigned = vec![None; slots.len()];
+    let mut owner = HashMap::new();
+    for slot in 0..slots.len() {
+        augment(
+            slot,
+            &candidates,
+            &settings.homebrew,
+            &mut assigned,
+            &mut owner,
+            &mut HashSet::new(),
+        );
+    }
+
+    slots
+        .into_iter()
+        .zip(assigned)
+        .filter_map(|(slot, monster)| monster.map(|monster| (slot, monster)))
+        .collect()
+}
+
+/// Tries to assign a monster to the slot, by moving other slots to other monsters if required.
+fn augment(
+    slot: usize,
+    candidates: &[Vec<AnyMonster>],
+    homebrew: &[Homebrew],
+    assigned: &mut [Option<AnyMonster>],
+    owner: &mut HashMap<AnyMonster, usize>,
+    visited: &mut HashSet<AnyMonster>,
+) -> bool {
+    for monster in &candidates[slot] {
+        let miniature = monster.sculpt(homebrew);
+        if !visited.insert(miniature) {
+            continue;
+        }
+        let free = match owner.get(&miniature) {
+            None => true,
+            Some(&other) => augment(other, candidates, homebrew, assigned, owner, visited),
+        };
+        if free {
+            owner.insert(miniature, slot);
+            assigned[slot] = Some(*monster);
+            return true;
+        }
+    }
+    false
+}
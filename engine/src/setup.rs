@@ -0,0 +1,373 @@
+use crate::csv::Csv;
+pub use crate::csv::CsvError;
+use crate::game::{Chapter, Color, Content, GameLanguage, Level, Monster, Number, SETUPS};
+use crate::setup_row::{Kind, NAME_COLUMN, read_kind, read_slots};
+use core::fmt::Write;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Setup {
+    pub content: Content,
+    pub chapter: Chapter,
+    pub kind: SetupKind,
+    /// the translated labels, English first; empty if the setup has no label
+    pub labels: &'static [(GameLanguage, &'static str)],
+    pub monsters: &'static [SetupItem],
+}
+
+/// Identifies a setup of [`SETUPS`] by its content, chapter, kind and English label.
+///
+/// Unlike the index it stays the same when setups are added, thus it's the one to store.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SetupKey {
+    pub content: Content,
+    pub chapter: Chapter,
+    pub kind: SetupKind,
+    /// the English label, empty if the setup has no label
+    pub label: String,
+}
+
+impl SetupKey {
+    /// The index into [`SETUPS`], `None` if there is no such setup.
+    pub fn index(&self) -> Option<usize> {
+        SETUPS.iter().position(|setup| {
+            setup.content == self.content
+                && setup.chapter == self.chapter
+                && setup.kind == self.kind
+                && setup.label(GameLanguage::En) == self.label
+        })
+    }
+}
+
+/// A setup imported by the user, see [`import`].
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CustomSetup {
+    pub chapter: Chapter,
+    pub kind: SetupKind,
+    /// the translated labels, English first; empty if the setup has no label
+    pub labels: Vec<(GameLanguage, String)>,
+    pub monsters: Vec<SetupItem>,
+}
+
+/// What a setup of a chapter is, in the order they are shown.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize)]
+pub enum SetupKind {
+    /// The setup at the start of the chapter, alternative setups have a label (e.g. "A").
+    Setup,
+    /// A door with a number.
+    Door(u8),
+    /// A door with a name, which is the label.
+    NamedDoor,
+    /// A page of the rulebook.
+    Page(u16),
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SetupItem {
+    pub number: Number,
+    pub color: Color,
+    pub level: Level,
+    #[serde(deserialize_with = "crate::game::deserialize_monster")]
+    pub monster: Option<Monster>,
+    pub exclude: bool,
+    /// the color and/or level are drawn as well, only for random monsters
+    #[serde(default)]
+    pub random: Option<RandomSlot>,
+}
+
+/// How the color and level of a random slot are drawn, see [`SetupItem::choices`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct RandomSlot {
+    /// any regular color (white, gray or black) instead of the color of the item
+    pub any_color: bool,
+    /// the highest level, the level of the item is the lowest one
+    pub max_level: Level,
+}
+
+const REGULAR_COLORS: [Color; 3] = [Color::White, Color::Gray, Color::Black];
+
+const LEVELS: [Level; 4] = [
+    Level::Rookie,
+    Level::Fighter,
+    Level::Veteran,
+    Level::Champion,
+];
+
+impl SetupItem {
+    /// The colors and levels the item can have, more than one if they are drawn.
+    pub fn choices(&self) -> Vec<(Color, Level)> {
+        let Some(random) = self.random.filter(|_| self.monster.is_none()) else {
+            return vec![(self.color, self.level)];
+        };
+        let colors = if random.any_color {
+            REGULAR_COLORS.to_vec()
+        } else {
+            vec![self.color]
+        };
+        colors
+            .into_iter()
+            .flat_map(|color| {
+                LEVELS
+                    .into_iter()
+                    .filter(|level| self.level <= *level && *level <= random.max_level)
+                    .map(move |level| (color, level))
+            })
+            .collect()
+    }
+}
+
+impl Setup {
+    /// The name in the language, the label is English if it's not translated.
+    pub fn name(&self, game_language: GameLanguage) -> String {
+        name(self.kind, self.label(game_language), game_language)
+    }
+
+    /// The label in the language, the English one if it's not translated.
+    pub fn label(&self, game_language: GameLanguage) -> &'static str {
+        label(self.labels.iter().copied(), game_language)
+    }
+
+    pub fn is_translated(&self, game_language: GameLanguage) -> bool {
+        is_translated(self.labels.iter().map(|(l, _)| *l), game_language)
+    }
+
+    pub fn key(&self) -> SetupKey {
+        SetupKey {
+            content: self.content,
+            chapter: self.chapter,
+            kind: self.kind,
+            label: self.label(GameLanguage::En).to_string(),
+        }
+    }
+}
+
+impl CustomSetup {
+    /// The name in the language, the label is English if it's not translated.
+    pub fn name(&self, game_language: GameLanguage) -> String {
+        name(self.kind, self.label(game_language), game_language)
+    }
+
+    /// The label in the language, the English one if it's not translated.
+    pub fn label(&self, game_language: GameLanguage) -> &str {
+        label(
+            self.labels.iter().map(|(l, label)| (*l, label.as_str())),
+            game_language,
+        )
+    }
+
+    pub fn is_translated(&self, game_language: GameLanguage) -> bool {
+        is_translated(self.labels.iter().map(|(l, _)| *l), game_language)
+    }
+}
+
+fn name(kind: SetupKind, label: &str, game_language: GameLanguage) -> String {
+    match kind {
+        SetupKind::Setup if label.is_empty() => game_language.setup_kind_setup().to_string(),
+        SetupKind::Setup => format!("{} {label}", game_language.setup_kind_setup()),
+        SetupKind::Door(number) => format!("{} {number}", game_language.setup_kind_door()),
+        SetupKind::NamedDoor => label.to_string(),
+        SetupKind::Page(page) => format!("{} {page}", game_language.setup_kind_page()),
+    }
+}
+
+fn label<'a>(
+    mut labels: impl Iterator<Item = (GameLanguage, &'a str)> + Clone,
+    game_language: GameLanguage,
+) -> &'a str {
+    labels
+        .clone()
+        .find(|(language, _)| *language == game_language)
+        .or(labels.next())
+        .map_or("", |(_, label)| label)
+}
+
+fn is_translated(
+    languages: impl Iterator<Item = GameLanguage>,
+    game_language: GameLanguage,
+) -> bool {
+    let mut languages = languages.peekable();
+    languages.peek().is_none() || languages.any(|language| language == game_language)
+}
+
+/// The content column of imported setups.
+pub const CUSTOM_CONTENT: &str = "Custom";
+
+/// Exports setups in the format of [`import`], which results in the same setups.
+///
+/// There is a name column for each translation of a label. Random colors and levels can't be
+/// exported, as they don't exist in that format.
+pub fn export(setups: &[CustomSetup]) -> String {
+    let languages = GameLanguage::iter()
+        .filter(|language| *language != GameLanguage::En)
+        .filter(|language| {
+            setups
+                .iter()
+                .any(|setup| setup.labels.iter().any(|(l, _)| l == language))
+        })
+        .collect::<Vec<_>>();
+
+    let mut text = String::from("Content,Chapter,Kind,Name");
+    for language in &languages {
+        let _ = write!(text, ",Name {} ({})", language.as_str(), language.code());
+    }
+    text.push('\n');
+    for setup in setups {
+        let kind = match setup.kind {
+            SetupKind::Setup => "Setup".to_string(),
+            SetupKind::Door(number) => format!("Door {number}"),
+            SetupKind::NamedDoor => "Door".to_string(),
+            SetupKind::Page(page) => format!("Page {page}"),
+        };
+        let labels = Some(GameLanguage::En)
+            .iter()
+            .chain(&languages)
+            .map(|language| {
+                setup
+                    .labels
+                    .iter()
+                    .find(|(l, _)| l == language)
+                    .map_or("", |(_, label)| label.as_str())
+            })
+            .collect::<Vec<_>>();
+        let mut fields = vec![
+            CUSTOM_CONTENT.to_string(),
+            setup.chapter.0.to_string(),
+            kind,
+        ];
+        fields.extend(labels.into_iter().map(quote));
+        for item in &setup.monsters {
+            fields.push(slot(item));
+            fields.push(item.monster.map_or(String::new(), |monster| {
+                let special = if monster.color().is_any_special() {
+                    "*"
+                } else {
+                    ""
+                };
+                quote(&format!("{special}{}", monster.name(GameLanguage::En)))
+            }));
+        }
+        text.push_str(&fields.join(","));
+        text.push('\n');
+    }
+    text
+}
+
+/// The slot of an item as in `setup.csv`, e.g. `W1 Ro`, `C2` or `Exclude`.
+fn slot(item: &SetupItem) -> String {
+    if item.exclude {
+        return "Exclude".to_string();
+    }
+    let color = match item.color {
+        Color::White => 'W',
+        Color::Gray => 'G',
+        Color::Black => 'B',
+        Color::Commander => 'C',
+        Color::Special | Color::SpecialCommander => 'S',
+    };
+    let level = match item.level {
+        Level::Rookie => "Ro",
+        Level::Fighter => "Fi",
+        Level::Veteran => "Ve",
+        Level::Champion => "Ch",
+    };
+    if item.color.is_any_commander() || item.color.is_any_special() {
+        format!("{color}{}", item.number.as_str())
+    } else {
+        format!("{color}{} {level}", item.number.as_str())
+    }
+}
+
+/// Quotes a field if it contains a comma or a quote.
+fn quote(field: &str) -> String {
+    if field.contains([',', '"']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Imports setups in the format of `setup.csv`, all with the content [`CUSTOM_CONTENT`].
+///
+/// The header is `Content,Chapter,Kind,Name` followed by `Name <Language> (<code>)` for each
+/// translation; the monsters follow the header.
+pub fn import(text: &str) -> Result<Vec<CustomSetup>, CsvError> {
+    let csv = Csv::parse("import", text)?;
+    let header = &csv.header;
+    for (index, column) in ["Content", "Chapter", "Kind", "Name"].iter().enumerate() {
+        if header.field(index) != *column {
+            return Err(header.error(index, format!("expected the column {column}")));
+        }
+    }
+    let name_columns = (NAME_COLUMN + 1..header.len())
+        .map(|index| {
+            let column = header.field(index);
+            GameLanguage::iter()
+                .filter(|language| *language != GameLanguage::En)
+                .find(|language| {
+                    column == format!("Name {} ({})", language.as_str(), language.code())
+                })
+                .map(|language| (index, language))
+                .ok_or_else(|| header.error(index, format!("unknown name column: {column}")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let first_monster = header.len();
+
+    let mut setups = Vec::<CustomSetup>::new();
+    for row in &csv.rows {
+        row.check_len(first_monster, None)?;
+        if row.field(0) != CUSTOM_CONTENT {
+            return Err(row.error(0, format!("the content has to be {CUSTOM_CONTENT}")));
+        }
+        let chapter = row
+            .field(1)
+            .parse()
+            .map(Chapter)
+            .map_err(|_| row.error(1, format!("unknown chapter: {}", row.field(1))))?;
+        if setups.last().is_some_and(|last| last.chapter > chapter) {
+            return Err(row.error(1, "wrong order: the chapter decreased"));
+        }
+
+        let translations = name_columns
+            .iter()
+            .map(|(index, language)| (*language, row.field(*index)))
+            .filter(|(_, label)| !label.is_empty())
+            .collect::<Vec<_>>();
+        let kind = match read_kind(row, !translations.is_empty())? {
+            Kind::Setup => SetupKind::Setup,
+            Kind::Door(number) => SetupKind::Door(number),
+            Kind::NamedDoor => SetupKind::NamedDoor,
+            Kind::Page(page) => SetupKind::Page(page),
+        };
+        let en = row.field(NAME_COLUMN);
+        let labels = Some((GameLanguage::En, en))
+            .filter(|_| !en.is_empty())
+            .into_iter()
+            .chain(translations)
+            .map(|(language, label)| (language, label.to_string()))
+            .collect();
+
+        let monsters = read_slots(row, first_monster, |name| {
+            Monster::iter()
+                .find(|monster| monster.name(GameLanguage::En) == name)
+                .map(|monster| (monster, monster.color()))
+        })?
+        .into_iter()
+        .map(|slot| SetupItem {
+            number: slot.number,
+            color: slot.color,
+            level: slot.level,
+            monster: slot.monster,
+            exclude: slot.exclude,
+            random: None,
+        })
+        .collect();
+
+        setups.push(CustomSetup {
+            chapter,
+            kind,
+            labels,
+            monsters,
+        });
+    }
+    Ok(setups)
+}
@@ -0,0 +1,177 @@
+//! The rules of a row of `setup.csv`, the generator includes this file as well.
+//!
+//! A row is `Content,Chapter,Kind,Name,<translated names>...,<monsters>...` where each monster
+//! is a pair of fields: the slot (e.g. `W1 Ro`, `C2`, `S1` or `Exclude`) and the monster
+//! (empty for a random one, prefixed by `*` for a special one).
+
+use crate::csv::{CsvError, Row};
+use crate::slot::{Color, Level, Number};
+
+/// The column of the kind.
+pub(crate) const KIND_COLUMN: usize = 2;
+/// The column of the (English) name.
+pub(crate) const NAME_COLUMN: usize = 3;
+
+/// The kind of a setup, see `engine::setup::SetupKind`.
+#[derive(Clone, Copy)]
+pub(crate) enum Kind {
+    Setup,
+    Door(u8),
+    NamedDoor,
+    Page(u16),
+}
+
+/// A monster of a setup, `M` is the type of the monster.
+pub(crate) struct Slot<M> {
+    pub(crate) number: Number,
+    pub(crate) color: Color,
+    pub(crate) level: Level,
+    pub(crate) monster: Option<M>,
+    pub(crate) exclude: bool,
+}
+
+const COLORS: [(char, Color); 5] = [
+    ('W', Color::White),
+    ('G', Color::Gray),
+    ('B', Color::Black),
+    ('C', Color::Commander),
+    ('S', Color::Special),
+];
+
+const LEVELS: [(&str, Level); 4] = [
+    ("Rookie", Level::Rookie),
+    ("Fighter", Level::Fighter),
+    ("Veteran", Level::Veteran),
+    ("Champion", Level::Champion),
+];
+
+const NUMBERS: [(char, Number); 5] = [
+    ('1', Number::One),
+    ('2', Number::Two),
+    ('3', Number::Three),
+    ('4', Number::Four),
+    ('5', Number::Five),
+];
+
+/// Reads the kind: `Setup`, `Door <number>`, `Door` (with a name) or `Page <number>`.
+///
+/// Numbered doors and pages have no name, the other ones need one except for the setup.
+pub(crate) fn read_kind(row: &Row, has_translation: bool) -> Result<Kind, CsvError> {
+    let field = row.field(KIND_COLUMN);
+    let kind = match field.split_once(' ') {
+        None if field == "Setup" => Some(Kind::Setup),
+        None if field == "Door" => Some(Kind::NamedDoor),
+        Some(("Door", number)) => number.parse().ok().map(Kind::Door),
+        Some(("Page", page)) => page.parse().ok().map(Kind::Page),
+        _ => None,
+    }
+    .ok_or_else(|| row.error(KIND_COLUMN, format!("unknown kind: {field}")))?;
+
+    let has_name = !row.field(NAME_COLUMN).is_empty();
+    match kind {
+        Kind::Door(_) | Kind::Page(_) if has_name || has_translation => {
+            Err(row.error(NAME_COLUMN, format!("a \"{field}\" has no name")))
+        }
+        Kind::NamedDoor if !has_name => Err(row.error(NAME_COLUMN, "missing name of the door")),
+        _ if !has_name && has_translation => {
+            Err(row.error(NAME_COLUMN, "translation without a name"))
+        }
+        _ => Ok(kind),
+    }
+}
+
+/// Reads the monsters, which start at the column `first`.
+///
+/// `find_monster` looks up a monster by its English name and returns it with its color.
+pub(crate) fn read_slots<M: Copy>(
+    row: &Row,
+    first: usize,
+    find_monster: impl Fn(&str) -> Option<(M, Color)>,
+) -> Result<Vec<Slot<M>>, CsvError> {
+    let mut slots = Vec::new();
+    let mut last_number = Number::One;
+    for index in (first..row.len()).step_by(2) {
+        let (f1, f2) = (row.field(index), row.field(index + 1));
+        if f1.is_empty() && f2.is_empty() {
+            continue;
+        }
+        let error = |reason: &str| row.error(index, format!("{reason}: \"{f1}\""));
+        let monster_error = |reason: &str| row.error(index + 1, format!("{reason}: \"{f2}\""));
+
+        let (number, mut color, level, exclude) = if f1 == "Exclude" {
+            (Number::One, Color::White, Level::Rookie, true)
+        } else {
+            let (f1_co_num, f1_le) = f1.split_once(' ').unwrap_or((f1, ""));
+            let mut chars = f1_co_num.chars();
+            let (Some(co), Some(nu), None) = (chars.next(), chars.next(), chars.next()) else {
+                return Err(error("slot is not a color and a number"));
+            };
+            let (_, co) = COLORS
+                .into_iter()
+                .find(|(c, _)| *c == co)
+                .ok_or_else(|| error("unknown color"))?;
+            let (_, nu) = NUMBERS
+                .into_iter()
+                .find(|(n, _)| *n == nu)
+                .ok_or_else(|| error("unknown number"))?;
+
+            let le = match co {
+                Color::Commander | Color::Special | Color::SpecialCommander => {
+                    if f1_le.is_empty() {
+                        Level::Rookie
+                    } else {
+                        return Err(error("unknown commander/special level"));
+                    }
+                }
+                _ => LEVELS
+                    .into_iter()
+                    .find(|(name, _)| !f1_le.is_empty() && name.starts_with(f1_le))
+                    .map(|(_, level)| level)
+                    .ok_or_else(|| error("unknown regular level"))?,
+            };
+
+            if nu < last_number {
+                return Err(error("number decreased"));
+            }
+            last_number = nu;
+
+            (nu, co, le, false)
+        };
+
+        let monster = if f2.is_empty() {
+            if color.is_any_special() {
+                return Err(error("special without monster"));
+            }
+            None
+        } else {
+            let (sp, f2) = f2.strip_prefix('*').map_or((false, f2), |f2| (true, f2));
+
+            let (monster, monster_color) =
+                find_monster(f2).ok_or_else(|| monster_error("unknown monster"))?;
+
+            if !(exclude
+                || (!sp && !color.is_any_special() && color == monster_color)
+                || (sp && color.is_any_special() && monster_color.is_any_special()))
+            {
+                return Err(monster_error(&format!(
+                    "color mismatch sp={sp:#?}, monster-color:{monster_color:?}"
+                )));
+            }
+
+            if color.is_any_special() && monster_color == Color::SpecialCommander {
+                color = Color::SpecialCommander;
+            }
+
+            Some(monster)
+        };
+
+        slots.push(Slot {
+            number,
+            color,
+            level,
+            monster,
+            exclude,
+        });
+    }
+    Ok(slots)
+}
@@ -0,0 +1,67 @@
+//! The color, level and number of a monster of a setup, the generator includes this file as well.
+
+use enum_tools::EnumTools;
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+#[derive(
+    Debug, Copy, Clone, EnumTools, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize,
+)]
+#[enum_tools(from_str, iter, into)]
+#[repr(u8)]
+pub enum Color {
+    White,
+    Gray,
+    Black,
+    Commander,
+    Special,
+    SpecialCommander,
+}
+
+impl Color {
+    pub fn is_any_commander(self) -> bool {
+        self == Color::Commander || self == Color::SpecialCommander
+    }
+
+    pub fn is_any_special(self) -> bool {
+        self == Color::Special || self == Color::SpecialCommander
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum Level {
+    Rookie,
+    Fighter,
+    Veteran,
+    Champion,
+}
+
+impl Level {
+    pub fn id(self) -> &'static str {
+        match self {
+            Level::Rookie => "ro",
+            Level::Fighter => "fi",
+            Level::Veteran => "ve",
+            Level::Champion => "ch",
+        }
+    }
+}
+
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, EnumTools, Ord, PartialOrd, Deserialize_repr, Serialize_repr,
+)]
+#[enum_tools(as_str, iter, try_from)]
+#[repr(u8)]
+#[allow(dead_code)] // only constructed via enum_tools
+pub enum Number {
+    #[enum_tools(rename = "1")]
+    One = 1,
+    #[enum_tools(rename = "2")]
+    Two = 2,
+    #[enum_tools(rename = "3")]
+    Three = 3,
+    #[enum_tools(rename = "4")]
+    Four = 4,
+    #[enum_tools(rename = "5")]
+    Five = 5,
+}
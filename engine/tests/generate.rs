@@ -0,0 +1,220 @@
+use engine::game::{Chapter, Color, Level, Number};
+use engine::generate::{COMMANDER_THREAT, adventure, door, setup_threat};
+use engine::setup::{SetupKind, export, import};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const NUMBERS: [Number; 5] = [
+    Number::One,
+    Number::Two,
+    Number::Three,
+    Number::Four,
+    Number::Five,
+];
+
+/// The number of random cases of each property test.
+const CASES: u64 = 2000;
+
+#[test]
+fn door_patterns() {
+    for seed in 0..CASES {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let players = NUMBERS[rng.random_range(0..NUMBERS.len())];
+        let budget = rng.random_range(0..40);
+        let chapter = Chapter(rng.random_range(1..=17));
+        let commander = rng.random_bool(0.3);
+        let items = door(players, budget, chapter, commander, &mut rng);
+        let context = format!("seed {seed}: {items:?}");
+
+        assert!(
+            items.windows(2).all(|w| w[0].number <= w[1].number),
+            "{context}"
+        );
+        assert!(
+            items
+                .iter()
+                .all(|item| item.monster.is_none() && !item.exclude && item.random.is_none()),
+            "{context}"
+        );
+        // no monster twice
+        assert!(
+            items.iter().enumerate().all(|(index, item)| {
+                !items[..index].iter().any(|other| {
+                    (other.number, other.color, other.level)
+                        == (item.number, item.color, item.level)
+                })
+            }),
+            "{context}"
+        );
+        let commanders = items
+            .iter()
+            .filter(|item| item.color == Color::Commander)
+            .collect::<Vec<_>>();
+        if commander {
+            // the commander is alone
+            assert_eq!(commanders.len(), 1, "{context}");
+            assert_eq!(commanders[0].number, Number::One, "{context}");
+            assert_eq!(
+                items
+                    .iter()
+                    .filter(|item| item.number == Number::One)
+                    .count(),
+                1,
+                "{context}"
+            );
+        } else {
+            assert!(commanders.is_empty(), "{context}");
+            // each number has a monster
+            for number in NUMBERS {
+                assert!(items.iter().any(|item| item.number == number), "{context}");
+            }
+        }
+    }
+}
+
+#[test]
+fn door_levels() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let levels = |chapter, rng: &mut StdRng| {
+        door(Number::Five, 100, Chapter(chapter), false, rng)
+            .into_iter()
+            .map(|item| item.level)
+            .collect::<Vec<_>>()
+    };
+    for _ in 0..CASES / 10 {
+        assert!(levels(1, &mut rng).iter().all(|l| *l == Level::Rookie));
+        assert!(
+            levels(2, &mut rng)
+                .iter()
+                .all(|l| *l == Level::Rookie || *l == Level::Fighter)
+        );
+        assert!(levels(4, &mut rng).iter().all(|l| *l == Level::Fighter));
+        assert!(levels(12, &mut rng).iter().all(|l| *l == Level::Champion));
+    }
+
+    // a low threat doesn't lower the level below the one of the chapter
+    let items = door(Number::Two, 0, Chapter(7), false, &mut rng);
+    assert!(items.iter().all(|item| item.level == Level::Veteran));
+    assert!(items.iter().all(|item| item.color == Color::White));
+}
+
+#[test]
+fn door_threat() {
+    for seed in 0..CASES {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let players = NUMBERS[rng.random_range(0..NUMBERS.len())];
+        let budget = rng.random_range(10..40);
+        let items = door(players, budget, Chapter(1), false, &mut rng);
+        assert!(setup_threat(&items, players) <= budget);
+
+        let items = door(
+            players,
+            budget + COMMANDER_THREAT,
+            Chapter(1),
+            true,
+            &mut rng,
+        );
+        assert!(setup_threat(&items, players) <= budget + COMMANDER_THREAT);
+
+        // the escorts of one player share the threat left by the commander
+        let items = door(
+            Number::One,
+            budget + COMMANDER_THREAT,
+            Chapter(1),
+            true,
+            &mut rng,
+        );
+        assert!(setup_threat(&items, Number::Five) <= budget + COMMANDER_THREAT);
+    }
+
+    // the same seed generates the same door
+    let generate = || {
+        door(
+            Number::Four,
+            20,
+            Chapter(5),
+            true,
+            &mut StdRng::seed_from_u64(7),
+        )
+    };
+    assert_eq!(generate(), generate());
+}
+
+#[test]
+fn adventure_curve() {
+    for seed in 0..CASES / 10 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let doors = rng.random_range(0..=6);
+        let commander = rng.random_bool(0.5);
+        let setups = adventure(Number::Three, 8, Chapter(4), doors, commander, &mut rng);
+        let context = format!("seed {seed}: {setups:?}");
+
+        assert_eq!(setups.len(), usize::from(doors) + 1, "{context}");
+        assert_eq!(setups[0].kind, SetupKind::Setup, "{context}");
+        for (door, setup) in setups.iter().enumerate().skip(1) {
+            assert_eq!(
+                setup.kind,
+                SetupKind::Door(u8::try_from(door).unwrap()),
+                "{context}"
+            );
+        }
+        assert!(setups.iter().all(|setup| setup.chapter == Chapter(4)));
+
+        // the levels rise from rookie to champion
+        let levels = setups
+            .iter()
+            .map(|setup| {
+                setup
+                    .monsters
+                    .iter()
+                    .filter(|item| item.color != Color::Commander)
+                    .map(|item| item.level)
+                    .min()
+            })
+            .collect::<Vec<_>>();
+        assert!(levels.windows(2).all(|w| w[0] <= w[1]), "{context}");
+        assert!(
+            setups[0]
+                .monsters
+                .iter()
+                .all(|item| item.level == Level::Rookie),
+            "{context}"
+        );
+        if doors > 0 {
+            assert_eq!(
+                levels[usize::from(doors)],
+                Some(Level::Champion),
+                "{context}"
+            );
+        }
+
+        // only the last door may have the commander
+        for (part, setup) in setups.iter().enumerate() {
+            let has_commander = setup
+                .monsters
+                .iter()
+                .any(|item| item.color == Color::Commander);
+            assert_eq!(
+                has_commander,
+                commander && doors > 0 && part == usize::from(doors),
+                "{context}"
+            );
+        }
+
+        // it can be saved as text
+        assert_eq!(import(&export(&setups)), Ok(setups), "seed {seed}");
+    }
+
+    // the same seed generates the same adventure
+    let generate = || {
+        adventure(
+            Number::Five,
+            10,
+            Chapter(1),
+            3,
+            true,
+            &mut StdRng::seed_from_u64(42),
+        )
+    };
+    assert_eq!(generate(), generate());
+}
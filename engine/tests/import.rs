@@ -0,0 +1,111 @@
+use engine::game::{Chapter, Color, GameLanguage, Level, Monster, Number, SETUPS};
+use engine::setup::{SetupItem, SetupKind, export, import};
+
+const HEADER: &str = "Content,Chapter,Kind,Name,Name German (de)\n";
+
+fn error(rows: &str) -> String {
+    import(&format!("{HEADER}{rows}"))
+        .expect_err("import should fail")
+        .to_string()
+}
+
+#[test]
+fn import_setups() {
+    let setups = import(&format!(
+        "{HEADER}\
+         # a comment\n\
+         Custom,1,Setup,,,W1 Ro,Skeleton Archer,G2 Fi,,Exclude,Shadow Vampire\n\
+         Custom,1,Door,A new room,Ein neuer Raum,C1,\n\
+         Custom,2,Page 3,,,S1,*Drifter Apparition\n"
+    ))
+    .expect("import should succeed");
+
+    assert_eq!(setups.len(), 3);
+    assert_eq!(setups[0].chapter, Chapter(1));
+    assert_eq!(setups[0].kind, SetupKind::Setup);
+    assert!(setups[0].labels.is_empty());
+    assert_eq!(
+        setups[0].monsters[..2],
+        [
+            SetupItem {
+                number: Number::One,
+                color: Color::White,
+                level: Level::Rookie,
+                monster: Some(Monster::SkeletonArcher),
+                exclude: false,
+                random: None,
+            },
+            SetupItem {
+                number: Number::Two,
+                color: Color::Gray,
+                level: Level::Fighter,
+                monster: None,
+                exclude: false,
+                random: None,
+            },
+        ]
+    );
+    assert!(setups[0].monsters[2].exclude);
+    assert_eq!(setups[1].kind, SetupKind::NamedDoor);
+    assert_eq!(setups[1].name(GameLanguage::De), "Ein neuer Raum");
+    assert_eq!(setups[2].kind, SetupKind::Page(3));
+    assert!(setups[2].monsters[0].color.is_any_special());
+}
+
+#[test]
+fn export_setups() {
+    let text = format!(
+        "{HEADER}\
+         Custom,1,Setup,,,W1 Ro,Skeleton Archer,G2 Fi,,C3,,Exclude,Shadow Vampire\n\
+         Custom,1,Door,\"Doors, \"\"old\"\" and new\",,B1 Ve,\n\
+         Custom,2,Page 3,,,S1,*Drifter Apparition\n\
+         Custom,2,Door 1,,,W1 Ch,\n\
+         Custom,3,Door,A new room,Ein neuer Raum,C1,\n"
+    );
+    let setups = import(&text).expect("import should succeed");
+    assert_eq!(import(&export(&setups)), Ok(setups.clone()));
+    assert!(export(&setups).starts_with(HEADER));
+    assert!(export(&setups[..1]).starts_with("Content,Chapter,Kind,Name\n"));
+}
+
+#[test]
+fn import_errors() {
+    assert_eq!(
+        error("Core,1,Setup,,,W1 Ro,\n"),
+        "import:2:1: the content has to be Custom"
+    );
+    assert_eq!(
+        error("Custom,1,Setup,,,W2 Ro,,W1 Ro,\n"),
+        "import:2:25: number decreased: \"W1 Ro\""
+    );
+    assert_eq!(
+        error("Custom,1,Setup,,,X1 Ro,\n"),
+        "import:2:18: unknown color: \"X1 Ro\""
+    );
+    assert_eq!(
+        error("Custom,1,Setup,,,S1,Drifter Apparition\n"),
+        "import:2:21: color mismatch sp=false, monster-color:Special: \"Drifter Apparition\""
+    );
+    assert_eq!(
+        error("Custom,1,Door 1,A,,W1 Ro,\n"),
+        "import:2:17: a \"Door 1\" has no name"
+    );
+    assert_eq!(
+        error("Custom,2,Setup,,\nCustom,1,Setup,,\n"),
+        "import:3:8: wrong order: the chapter decreased"
+    );
+    assert_eq!(
+        import("Content,Chapter,Kind,Name,Name Klingon (tlh)\n")
+            .expect_err("import should fail")
+            .to_string(),
+        "import:1:27: unknown name column: Name Klingon (tlh)"
+    );
+}
+
+#[test]
+fn setup_keys() {
+    // each setup is found by its key
+    for (index, setup) in SETUPS.iter().enumerate() {
+        assert_eq!(setup.key().index(), Some(index), "{setup:?}");
+    }
+}
@@ -0,0 +1,561 @@
+use engine::game::{AnyMonster, Color, Content, GameLanguage, Level, Monster, Number};
+use engine::homebrew::{Homebrew, HomebrewId};
+use engine::roll::{Incomplete, Item, RollSettings, Shortage, figures, reroll, roll, shortages};
+use engine::setup::{RandomSlot, SetupItem};
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::{HashMap, HashSet};
+
+const COLORS: [Color; 4] = [Color::White, Color::Gray, Color::Black, Color::Commander];
+const LEVELS: [Level; 4] = [
+    Level::Rookie,
+    Level::Fighter,
+    Level::Veteran,
+    Level::Champion,
+];
+const NUMBERS: [Number; 5] = [
+    Number::One,
+    Number::Two,
+    Number::Three,
+    Number::Four,
+    Number::Five,
+];
+
+/// The number of random cases of each property test.
+const CASES: u64 = 2000;
+
+fn random_settings(rng: &mut StdRng) -> RollSettings {
+    let contents = Content::iter().collect::<Vec<_>>();
+    let mut content = contents
+        .iter()
+        .copied()
+        .filter(|_| rng.random_bool(0.4))
+        .collect::<HashSet<_>>();
+    content.insert(*contents.choose(rng).unwrap());
+    let homebrew = (0..rng.random_range(0..=3))
+        .map(|id| Homebrew {
+            id: HomebrewId(id),
+            names: vec![(GameLanguage::En, format!("Homebrew {id}"))],
+            color: *COLORS.choose(rng).unwrap(),
+            miniature: if rng.random_bool(0.5) {
+                Some(*Monster::iter().collect::<Vec<_>>().choose(rng).unwrap())
+            } else {
+                None
+            },
+        })
+        .collect::<Vec<_>>();
+    let banned = AnyMonster::iter(&homebrew)
+        .filter(|_| rng.random_bool(0.1))
+        .collect::<HashSet<_>>();
+    RollSettings {
+        content,
+        banned,
+        homebrew,
+        ..RollSettings::default()
+    }
+}
+
+fn random_slots(rng: &mut StdRng) -> Vec<SetupItem> {
+    let count = rng.random_range(1..=8);
+    (0..count)
+        .map(|_| {
+            let color = *COLORS.choose(rng).unwrap();
+            SetupItem {
+                number: *NUMBERS.choose(rng).unwrap(),
+                color,
+                level: if color == Color::Commander {
+                    Level::Rookie
+                } else {
+                    *LEVELS.choose(rng).unwrap()
+                },
+                monster: None,
+                exclude: false,
+                random: None,
+            }
+        })
+        .collect()
+}
+
+/// The distinct (color, level) of the slots, in the order of the slots.
+fn keys(slots: &[SetupItem]) -> Vec<(Color, Level)> {
+    let mut keys = Vec::new();
+    for slot in slots {
+        if !keys.contains(&(slot.color, slot.level)) {
+            keys.push((slot.color, slot.level));
+        }
+    }
+    keys
+}
+
+fn is_eligible(settings: &RollSettings, monster: AnyMonster) -> bool {
+    let enabled = match monster {
+        AnyMonster::Game(monster) => {
+            !monster.color().is_any_special() && settings.content.contains(&monster.content())
+        }
+        AnyMonster::Homebrew(_) => monster.homebrew(&settings.homebrew).is_some(),
+    };
+    enabled && !settings.banned.contains(&monster)
+}
+
+/// Whether each key can get an own miniature, by trying all assignments.
+fn is_feasible(
+    settings: &RollSettings,
+    keys: &[(Color, Level)],
+    used: &mut Vec<AnyMonster>,
+) -> bool {
+    let Some(((color, _), rest)) = keys.split_first() else {
+        return true;
+    };
+    let homebrew = &settings.homebrew;
+    AnyMonster::iter(homebrew)
+        .filter(|monster| {
+            monster.color(homebrew) == Some(*color) && is_eligible(settings, *monster)
+        })
+        .any(|monster| {
+            if used.contains(&monster.sculpt(homebrew)) {
+                return false;
+            }
+            used.push(monster.sculpt(homebrew));
+            let feasible = is_feasible(settings, rest, used);
+            used.pop();
+            feasible
+        })
+}
+
+fn roll_seeded(
+    settings: &RollSettings,
+    slots: &[SetupItem],
+    seed: u64,
+) -> Result<Vec<Item>, Incomplete> {
+    roll(settings, slots, &mut StdRng::seed_from_u64(seed))
+}
+
+#[test]
+fn monsters_match_their_slot() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for seed in 0..CASES {
+        let settings = random_settings(&mut rng);
+        let slots = random_slots(&mut rng);
+        let output =
+            roll_seeded(&settings, &slots, seed).unwrap_or_else(|Incomplete(output)| output);
+        assert_eq!(output.len(), slots.len());
+        for (item, slot) in output.iter().zip(&slots) {
+            assert_eq!(
+                (item.number, item.color, item.level),
+                (slot.number, slot.color, slot.level)
+            );
+            if let Some(monster) = item.monster {
+                assert_eq!(
+                    monster.color(&settings.homebrew),
+                    Some(item.color),
+                    "{monster:?} in {item:?}"
+                );
+                assert!(item.missing.is_none());
+            } else {
+                assert!(item.missing.is_some());
+            }
+        }
+    }
+}
+
+#[test]
+fn no_shared_miniature() {
+    let mut rng = StdRng::seed_from_u64(2);
+    for seed in 0..CASES {
+        let settings = random_settings(&mut rng);
+        let slots = random_slots(&mut rng);
+        let output =
+            roll_seeded(&settings, &slots, seed).unwrap_or_else(|Incomplete(output)| output);
+        let mut drawn = HashMap::new();
+        for item in &output {
+            if let Some(monster) = item.monster
+                && let Some(other) = drawn.insert((item.color, item.level), monster)
+            {
+                assert_eq!(other, monster, "same slot with different monsters");
+            }
+        }
+        let sculpts = drawn
+            .values()
+            .map(|monster| monster.sculpt(&settings.homebrew))
+            .collect::<HashSet<_>>();
+        assert_eq!(sculpts.len(), drawn.len(), "shared miniature in {drawn:?}");
+    }
+}
+
+#[test]
+fn only_enabled_monsters() {
+    let mut rng = StdRng::seed_from_u64(3);
+    for seed in 0..CASES {
+        let settings = random_settings(&mut rng);
+        let slots = random_slots(&mut rng);
+        let output =
+            roll_seeded(&settings, &slots, seed).unwrap_or_else(|Incomplete(output)| output);
+        for monster in output.iter().filter_map(|item| item.monster) {
+            assert!(
+                is_eligible(&settings, monster),
+                "{monster:?} is disabled or banned"
+            );
+        }
+    }
+}
+
+#[test]
+fn incomplete_only_if_infeasible() {
+    let mut rng = StdRng::seed_from_u64(4);
+    for seed in 0..CASES {
+        let settings = random_settings(&mut rng);
+        let slots = random_slots(&mut rng);
+        let feasible = is_feasible(&settings, &keys(&slots), &mut Vec::new());
+        let result = roll_seeded(&settings, &slots, seed);
+        assert_eq!(result.is_ok(), feasible, "{slots:?} with {settings:?}");
+        if let Err(Incomplete(output)) = result {
+            assert!(output.iter().any(|item| item.missing.is_some()));
+        }
+    }
+}
+
+#[test]
+fn uniform_distribution() {
+    const RUNS: u64 = 10_000;
+
+    let settings = RollSettings {
+        content: Content::iter().collect(),
+        ..RollSettings::default()
+    };
+    for color in COLORS {
+        let slots = [SetupItem {
+            number: Number::One,
+            color,
+            level: Level::Rookie,
+            monster: None,
+            exclude: false,
+            random: None,
+        }];
+        let mut counts = AnyMonster::iter(&settings.homebrew)
+            .filter(|monster| {
+                monster.color(&settings.homebrew) == Some(color) && is_eligible(&settings, *monster)
+            })
+            .map(|monster| (monster, 0))
+            .collect::<HashMap<_, _>>();
+        for seed in 0..RUNS {
+            let output = roll_seeded(&settings, &slots, seed).unwrap();
+            *counts.get_mut(&output[0].monster.unwrap()).unwrap() += 1;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let expected = RUNS as f64 / counts.len() as f64;
+        for (monster, count) in counts {
+            let deviation = (f64::from(count) - expected).abs() / expected;
+            assert!(
+                deviation < 0.2,
+                "{monster:?} drawn {count} times, expected about {expected:.0}"
+            );
+        }
+    }
+}
+
+#[test]
+fn homebrew_monsters() {
+    let homebrew = vec![
+        Homebrew {
+            id: HomebrewId(1),
+            names: vec![(GameLanguage::En, "Own".to_string())],
+            color: Color::White,
+            miniature: None,
+        },
+        Homebrew {
+            id: HomebrewId(2),
+            names: vec![(GameLanguage::En, "Proxy".to_string())],
+            color: Color::White,
+            miniature: Some(Monster::SkeletonArcher),
+        },
+    ];
+    let settings = RollSettings {
+        homebrew,
+        ..RollSettings::default()
+    };
+    let slot = |number, level, monster| SetupItem {
+        number,
+        color: Color::White,
+        level,
+        monster,
+        exclude: false,
+        random: None,
+    };
+
+    // without any content only the homebrew monsters can be drawn
+    let output = roll_seeded(
+        &settings,
+        &[
+            slot(Number::One, Level::Rookie, None),
+            slot(Number::Two, Level::Fighter, None),
+        ],
+        0,
+    )
+    .unwrap();
+    let drawn = output
+        .iter()
+        .filter_map(|item| item.monster)
+        .collect::<HashSet<_>>();
+    assert_eq!(
+        drawn,
+        HashSet::from([
+            AnyMonster::Homebrew(HomebrewId(1)),
+            AnyMonster::Homebrew(HomebrewId(2))
+        ])
+    );
+
+    // the proxy shares the miniature of the preset monster
+    let output = roll_seeded(
+        &settings,
+        &[
+            slot(Number::One, Level::Rookie, Some(Monster::SkeletonArcher)),
+            slot(Number::Two, Level::Fighter, None),
+        ],
+        0,
+    )
+    .unwrap();
+    assert_eq!(output[1].monster, Some(AnyMonster::Homebrew(HomebrewId(1))));
+}
+
+#[test]
+fn figures_per_sculpt() {
+    let monster = Monster::iter()
+        .find(|monster| monster.miniature().is_some())
+        .unwrap();
+    let sculpt = monster.sculpt();
+    let item = |number, monster: Monster| Item {
+        number,
+        color: monster.color(),
+        level: Level::Rookie,
+        monster: Some(AnyMonster::Game(monster)),
+        preset: true,
+        missing: None,
+    };
+    let output = [
+        item(Number::One, monster),
+        item(Number::Two, sculpt),
+        item(Number::Three, monster),
+        Item {
+            monster: None,
+            ..item(Number::Four, monster)
+        },
+    ];
+    let mut settings = RollSettings::default();
+    assert_eq!(figures(&settings, &output), [(AnyMonster::Game(sculpt), 3)]);
+    settings.players = Some(Number::Two);
+    assert_eq!(figures(&settings, &output), [(AnyMonster::Game(sculpt), 2)]);
+}
+
+#[test]
+fn random_colors_and_levels() {
+    let settings = RollSettings {
+        content: Content::iter().collect(),
+        ..RollSettings::default()
+    };
+    let slot = |number, color, level, any_color, max_level| SetupItem {
+        number,
+        color,
+        level,
+        monster: None,
+        exclude: false,
+        random: Some(RandomSlot {
+            any_color,
+            max_level,
+        }),
+    };
+    let slots = [
+        slot(
+            Number::One,
+            Color::White,
+            Level::Fighter,
+            true,
+            Level::Fighter,
+        ),
+        slot(
+            Number::Two,
+            Color::White,
+            Level::Fighter,
+            true,
+            Level::Fighter,
+        ),
+        slot(
+            Number::Three,
+            Color::Gray,
+            Level::Rookie,
+            false,
+            Level::Veteran,
+        ),
+    ];
+
+    let mut colors = HashSet::new();
+    let mut levels = HashSet::new();
+    for seed in 0..CASES {
+        let output = roll_seeded(&settings, &slots, seed).unwrap();
+        assert_eq!(roll_seeded(&settings, &slots, seed).unwrap(), output);
+        for item in &output {
+            assert_eq!(
+                item.monster.and_then(|m| m.color(&settings.homebrew)),
+                Some(item.color)
+            );
+        }
+        // the same random slot has the same color and level
+        assert_eq!(
+            (output[0].color, output[0].level, output[0].monster),
+            (output[1].color, output[1].level, output[1].monster)
+        );
+        assert!(!output[0].color.is_any_commander() && !output[0].color.is_any_special());
+        assert_eq!(output[0].level, Level::Fighter);
+        assert_eq!(output[2].color, Color::Gray);
+        assert!(output[2].level <= Level::Veteran);
+        assert_ne!(
+            (output[0].color, output[0].level),
+            (output[2].color, output[2].level)
+        );
+        colors.insert(output[0].color);
+        levels.insert(output[2].level);
+    }
+    assert_eq!(colors.len(), 3);
+    assert_eq!(levels.len(), 3);
+}
+
+#[test]
+fn owned_copies() {
+    let mut sculpts = Monster::iter()
+        .filter(|monster| monster.content() == Content::Core && monster.color() == Color::White)
+        .map(|monster| AnyMonster::Game(monster.sculpt()))
+        .collect::<Vec<_>>();
+    sculpts.dedup();
+    let target = sculpts[0];
+    let slots = NUMBERS.map(|number| SetupItem {
+        number,
+        color: Color::White,
+        level: Level::Rookie,
+        monster: None,
+        exclude: false,
+        random: None,
+    });
+    let mut settings = RollSettings {
+        content: HashSet::from([Content::Core]),
+        copies: sculpts
+            .iter()
+            .map(|sculpt| (*sculpt, if *sculpt == target { 5 } else { 2 }))
+            .collect(),
+        ..RollSettings::default()
+    };
+
+    // only one sculpt has enough figures for all players
+    for seed in 0..100 {
+        let output = roll_seeded(&settings, &slots, seed).unwrap();
+        assert_eq!(output[0].monster.map(|m| m.sculpt(&[])), Some(target));
+        assert!(shortages(&settings, &output).is_empty());
+    }
+
+    // with two players all sculpts have enough figures
+    settings.players = Some(Number::Two);
+    let drawn = (0..100)
+        .map(|seed| roll_seeded(&settings, &slots, seed).unwrap()[0].monster)
+        .collect::<HashSet<_>>();
+    assert!(drawn.len() > 1);
+
+    // without enough figures the roll is still complete, but the shortage is reported
+    settings.players = None;
+    settings.copies.insert(target, 3);
+    let output = roll_seeded(&settings, &slots, 0).unwrap();
+    let sculpt = output[0].monster.unwrap().sculpt(&[]);
+    assert_eq!(
+        shortages(&settings, &output),
+        [Shortage {
+            sculpt,
+            needed: 5,
+            owned: if sculpt == target { 3 } else { 2 },
+        }]
+    );
+}
+
+#[test]
+fn reroll_prefers_fresh() {
+    let selected = [SetupItem {
+        number: Number::One,
+        color: Color::White,
+        level: Level::Rookie,
+        monster: None,
+        exclude: false,
+        random: None,
+    }];
+    let mut settings = RollSettings {
+        content: HashSet::from([Content::Core]),
+        ..RollSettings::default()
+    };
+    let output = roll_seeded(&settings, &selected, 5).unwrap();
+    let current = output[0].monster.unwrap();
+    let whites = AnyMonster::iter(&[])
+        .filter(|monster| is_eligible(&settings, *monster))
+        .filter(|monster| monster.color(&[]) == Some(Color::White))
+        .filter(|monster| *monster != current)
+        .collect::<Vec<_>>();
+    let fresh = whites[0];
+    settings.recent = whites[1..].iter().copied().collect();
+
+    for seed in 0..100 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let monster = reroll(
+            &settings,
+            &selected,
+            &output,
+            Color::White,
+            Level::Rookie,
+            &mut rng,
+        );
+        assert_eq!(monster, Some(fresh), "seed {seed}");
+    }
+}
+
+#[test]
+fn fixed_picks() {
+    let item = |color, monster| SetupItem {
+        number: Number::One,
+        color,
+        level: Level::Rookie,
+        monster,
+        exclude: false,
+        random: None,
+    };
+    let whites = Monster::iter()
+        .filter(|monster| monster.color() == Color::White && monster.miniature().is_none())
+        .collect::<Vec<_>>();
+    let (kept, banned, preset) = (whites[0], whites[1], whites[2]);
+    let roll_fixed = |settings: &RollSettings, selected: &[SetupItem]| {
+        let output = roll_seeded(settings, selected, 6).unwrap();
+        output
+            .iter()
+            .find(|item| !item.preset)
+            .and_then(|item| item.monster)
+    };
+    let fixed =
+        |monster| HashMap::from([((Color::White, Level::Rookie), AnyMonster::Game(monster))]);
+    let mut settings = RollSettings {
+        content: Content::iter().collect(),
+        fixed: fixed(kept),
+        ..RollSettings::default()
+    };
+    let random = [item(Color::White, None)];
+    assert_eq!(roll_fixed(&settings, &random), Some(AnyMonster::Game(kept)));
+
+    // a banned pick is drawn again
+    settings.fixed = fixed(banned);
+    settings.banned = HashSet::from([AnyMonster::Game(banned)]);
+    assert_ne!(
+        roll_fixed(&settings, &random),
+        Some(AnyMonster::Game(banned))
+    );
+
+    // a pick which is preset is drawn again
+    settings.fixed = fixed(preset);
+    let with_preset = [item(Color::White, None), item(Color::White, Some(preset))];
+    assert_ne!(
+        roll_fixed(&settings, &with_preset),
+        Some(AnyMonster::Game(preset))
+    );
+}
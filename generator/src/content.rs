@@ -0,0 +1,138 @@
+use crate::csv::{Csv, CsvError, Row};
+use crate::language::{Lng, NameColumns, Names, write_names};
+use anyhow::{anyhow, bail};
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+/// The columns before the translated names.
+const COLUMNS: usize = 4;
+
+static CONTENT_CSV: LazyLock<Result<Csv<'static>, CsvError>> =
+    LazyLock::new(|| Csv::parse("content.csv", include_str!("content.csv")));
+
+fn content_csv() -> anyhow::Result<&'static Csv<'static>> {
+    CONTENT_CSV.as_ref().map_err(|error| anyhow!("{error}"))
+}
+
+pub(crate) fn content_name_columns() -> anyhow::Result<NameColumns> {
+    NameColumns::parse(&content_csv()?.header, COLUMNS)
+}
+
+pub fn content(languages: &[Lng]) -> Result<(String, Vec<Cnt>), anyhow::Error> {
+    let csv = content_csv()?;
+    let name_columns = NameColumns::parse(&csv.header, COLUMNS)?;
+    let contents = csv
+        .rows
+        .iter()
+        .map(|row| content_read(&name_columns, csv.header.len(), row))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut idents = HashSet::new();
+    let mut image_prefixes = HashSet::new();
+    for (content, row) in contents.iter().zip(&csv.rows) {
+        if !idents.insert(content.ident) {
+            bail!(row.error(0, format!("Duplicate content: {}", content.ident)));
+        }
+        if !image_prefixes.insert(content.image_prefix) {
+            bail!(row.error(
+                1,
+                format!("Duplicate image prefix: {}", content.image_prefix),
+            ));
+        }
+    }
+
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "#[derive(Copy, Clone, Debug, EnumTools, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]"
+    )?;
+    writeln!(output, "#[repr(u8)]")?;
+    writeln!(output, "#[enum_tools(as_str, into, iter)]")?;
+    writeln!(output, "pub enum Content {{")?;
+    for content in &contents {
+        writeln!(output, "  {},", content.ident)?;
+    }
+    writeln!(output, "}}")?;
+    writeln!(output, "impl Content {{")?;
+    writeln!(output, "    pub fn image_prefix(self) -> &'static str {{")?;
+    writeln!(output, "        match self {{")?;
+    for content in &contents {
+        writeln!(
+            output,
+            "            Content::{} => {:?},",
+            content.ident, content.image_prefix
+        )?;
+    }
+    writeln!(output, "        }}")?;
+    writeln!(output, "    }}")?;
+    writeln!(output, "    pub fn order(self) -> u8 {{")?;
+    writeln!(output, "        #[allow(clippy::match_same_arms)]")?;
+    writeln!(output, "        match self {{")?;
+    for content in &contents {
+        writeln!(
+            output,
+            "            Content::{} => {},",
+            content.ident, content.order
+        )?;
+    }
+    writeln!(output, "        }}")?;
+    writeln!(output, "    }}")?;
+    write_names(
+        &mut output,
+        "Content",
+        &contents
+            .iter()
+            .map(|content| (content.ident, &content.names))
+            .collect::<Vec<_>>(),
+        languages,
+    )?;
+    writeln!(output, "}}")?;
+
+    Ok((output, contents))
+}
+
+pub(crate) struct Cnt {
+    pub(crate) ident: &'static str,
+    pub(crate) image_prefix: &'static str,
+    order: u8,
+    names: Names,
+}
+
+impl Cnt {
+    /// Finds a content by its identifier, returns its position (which is also its order in `SETUPS`).
+    pub(crate) fn find<'a>(contents: &'a [Cnt], ident: &str) -> anyhow::Result<(usize, &'a Cnt)> {
+        contents
+            .iter()
+            .enumerate()
+            .find(|(_, content)| content.ident == ident)
+            .ok_or_else(|| anyhow!("Unknown content: {ident}"))
+    }
+}
+
+fn content_read(
+    name_columns: &NameColumns,
+    columns: usize,
+    row: &'static Row<'static>,
+) -> anyhow::Result<Cnt> {
+    row.check_len(COLUMNS, Some(columns))?;
+    let ident = row.field(0);
+    if ident.is_empty() || !ident.chars().all(char::is_alphanumeric) {
+        bail!(row.error(0, format!("Invalid content: {ident}")));
+    }
+    let image_prefix = row.field(1);
+    if image_prefix.len() != 2 {
+        bail!(row.error(1, format!("Invalid image prefix: {image_prefix}")));
+    }
+    let order = u8::from_str(row.field(2))
+        .map_err(|_| row.error(2, format!("Invalid order: {}", row.field(2))))?;
+
+    Ok(Cnt {
+        ident,
+        image_prefix,
+        order,
+        names: name_columns.names(row.field(3), row),
+    })
+}
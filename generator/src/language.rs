@@ -0,0 +1,225 @@
+use crate::content::content_name_columns;
+use crate::csv::Row;
+use crate::monster::monster_name_columns;
+use crate::setup::setup_name_columns;
+use anyhow::bail;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// A game language, as found in the header of a data file.
+#[derive(Clone, PartialEq)]
+pub(crate) struct Lng {
+    /// used in `game.lrc` and to store the setting
+    pub(crate) code: &'static str,
+    pub(crate) name: &'static str,
+}
+
+impl Lng {
+    /// The language of the name columns, it's used when a translation is missing.
+    const ENGLISH: Lng = Lng {
+        code: "en",
+        name: "English",
+    };
+
+    pub(crate) fn ident(&self) -> String {
+        let mut chars = self.code.chars();
+        chars
+            .next()
+            .map(|c| c.to_ascii_uppercase())
+            .into_iter()
+            .chain(chars)
+            .collect()
+    }
+}
+
+/// The name of something in all languages.
+pub(crate) struct Names {
+    pub(crate) en: &'static str,
+    /// by language code, only the translated ones
+    translations: HashMap<&'static str, &'static str>,
+}
+
+impl Names {
+    /// Whether there is no name in any language.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.en.is_empty() && self.translations.is_empty()
+    }
+
+    pub(crate) fn get(&self, language: &Lng) -> Option<&'static str> {
+        if *language == Lng::ENGLISH {
+            Some(self.en)
+        } else {
+            self.translations.get(language.code).copied()
+        }
+    }
+}
+
+/// The columns with translated names of a data file.
+pub(crate) struct NameColumns(Vec<(usize, Lng)>);
+
+impl NameColumns {
+    /// Reads the header, all columns from `first` on have to be `Name <Language> (<code>)`.
+    pub(crate) fn parse(header: &'static Row<'static>, first: usize) -> anyhow::Result<Self> {
+        let columns = (first..header.len())
+            .map(|index| {
+                let column = header.field(index);
+                let (name, code) = column
+                    .strip_prefix("Name ")
+                    .and_then(|column| column.strip_suffix(')'))
+                    .and_then(|column| column.split_once(" ("))
+                    .ok_or_else(|| header.error(index, format!("Invalid name column: {column}")))?;
+                if code.is_empty() || !code.chars().all(|c| c.is_ascii_lowercase()) {
+                    bail!(header.error(index, format!("Invalid language code: {column}")));
+                }
+                if code == Lng::ENGLISH.code {
+                    bail!(header.error(
+                        index,
+                        format!("English is the name column itself: {column}"),
+                    ));
+                }
+                Ok((index, Lng { code, name }))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(NameColumns(columns))
+    }
+
+    /// The names of a line, empty fields are not translated.
+    pub(crate) fn names(&self, en: &'static str, row: &'static Row<'static>) -> Names {
+        Names {
+            en,
+            translations: self
+                .0
+                .iter()
+                .map(|(index, language)| (language.code, row.field(*index)))
+                .filter(|(_, name)| !name.is_empty())
+                .collect(),
+        }
+    }
+}
+
+/// All languages of the data files, English first.
+pub fn languages() -> Result<Vec<Lng>, anyhow::Error> {
+    let mut languages = vec![Lng::ENGLISH];
+    for (file, columns) in [
+        ("content.csv", content_name_columns()),
+        ("monster.csv", monster_name_columns()),
+        ("setup.csv", setup_name_columns()),
+    ] {
+        for (_, language) in columns?.0 {
+            match languages.iter().find(|l| l.code == language.code) {
+                None => languages.push(language),
+                Some(other) if other.name != language.name => bail!(
+                    "Language {} is named {} and {} in {file}",
+                    language.code,
+                    other.name,
+                    language.name
+                ),
+                Some(_) => {}
+            }
+        }
+    }
+    Ok(languages)
+}
+
+pub fn language(languages: &[Lng]) -> Result<String, anyhow::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "#[derive(Copy, Clone, Debug, Eq, PartialEq, EnumTools, TypedI18N, Serialize, Deserialize)]"
+    )?;
+    writeln!(output, "#[enum_tools(as_str, iter, names)]")?;
+    writeln!(output, "#[repr(usize)]")?;
+    writeln!(output, "#[typed_i18n(filename = \"game.lrc\")]")?;
+    writeln!(output, "#[typed_i18n(builder = \"static_str\")]")?;
+    writeln!(output, "pub enum GameLanguage {{")?;
+    for language in languages {
+        writeln!(output, "    #[serde(rename = {:?})]", language.code)?;
+        writeln!(output, "    #[enum_tools(rename = {:?})]", language.name)?;
+        writeln!(output, "    {},", language.ident())?;
+    }
+    writeln!(output, "}}")?;
+    writeln!(output, "impl GameLanguage {{")?;
+    writeln!(
+        output,
+        "    /// The code of the language, as in the header of the data files."
+    )?;
+    writeln!(output, "    pub fn code(self) -> &'static str {{")?;
+    writeln!(output, "        match self {{")?;
+    for language in languages {
+        writeln!(
+            output,
+            "            GameLanguage::{} => {:?},",
+            language.ident(),
+            language.code
+        )?;
+    }
+    writeln!(output, "        }}")?;
+    writeln!(output, "    }}")?;
+    writeln!(output, "}}")?;
+
+    Ok(output)
+}
+
+/// Writes `name` (with a fallback to English) and `is_translated` of an enum.
+pub(crate) fn write_names(
+    output: &mut String,
+    ty: &str,
+    entries: &[(&str, &Names)],
+    languages: &[Lng],
+) -> Result<(), anyhow::Error> {
+    writeln!(
+        output,
+        "    pub fn name(self, language: GameLanguage) -> &'static str {{"
+    )?;
+    writeln!(output, "        #[allow(clippy::match_same_arms)]")?;
+    writeln!(output, "        match language {{")?;
+    for language in languages {
+        writeln!(
+            output,
+            "            GameLanguage::{} => match self {{",
+            language.ident()
+        )?;
+        for (ident, names) in entries {
+            writeln!(
+                output,
+                "                {ty}::{ident} => {:?},",
+                names.get(language).unwrap_or(names.en)
+            )?;
+        }
+        writeln!(output, "            }},")?;
+    }
+    writeln!(output, "        }}")?;
+    writeln!(output, "    }}")?;
+    writeln!(
+        output,
+        "    pub fn is_translated(self, language: GameLanguage) -> bool {{"
+    )?;
+    writeln!(output, "        #[allow(clippy::match_same_arms)]")?;
+    writeln!(output, "        match language {{")?;
+    for language in languages {
+        let missing = entries
+            .iter()
+            .filter(|(_, names)| names.get(language).is_none())
+            .map(|(ident, _)| format!("{ty}::{ident}"))
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            writeln!(
+                output,
+                "            GameLanguage::{} => true,",
+                language.ident()
+            )?;
+        } else {
+            writeln!(
+                output,
+                "            GameLanguage::{} => !matches!(self, {}),",
+                language.ident(),
+                missing.join(" | ")
+            )?;
+        }
+    }
+    writeln!(output, "        }}")?;
+    writeln!(output, "    }}")?;
+
+    Ok(())
+}
@@ -1,3 +1,6 @@
+use crate::content::content;
+use crate::language::{language, languages};
+pub use crate::message::check_messages;
 use crate::monster::{monster, monster_image};
 use crate::setup::setup;
 use anyhow::Context;
@@ -5,15 +8,41 @@ use std::ffi::OsStr;
 use std::fs;
 use std::path::Path;
 
-mod game;
+mod content;
+#[path = "../../engine/src/csv.rs"]
+mod csv;
+mod language;
+mod message;
 mod monster;
 mod setup;
+#[path = "../../engine/src/setup_row.rs"]
+mod setup_row;
+// only the types are used
+#[allow(dead_code)]
+#[path = "../../engine/src/slot.rs"]
+mod slot;
 
 pub fn run(out_dir: &OsStr) -> Result<(), anyhow::Error> {
-    let (generated_monster, monsters) = monster().context("function monster")?;
-    let generated_setup = setup(&monsters).context("function setup")?;
+    let languages = languages().context("function languages")?;
+    let generated_language = language(&languages).context("function language")?;
+    let (generated_content, contents) = content(&languages).context("function content")?;
+    let (generated_monster, monsters) =
+        monster(&contents, &languages).context("function monster")?;
+    let generated_setup = setup(&contents, &monsters, &languages).context("function setup")?;
     let generated_monster_image = monster_image(&monsters).context("function monster_image")?;
 
+    fs::write(
+        Path::new(out_dir).join("generated_language.rs"),
+        &generated_language,
+    )
+    .context("Failed to write generated_language")?;
+
+    fs::write(
+        Path::new(out_dir).join("generated_content.rs"),
+        &generated_content,
+    )
+    .context("Failed to write generated_content")?;
+
     fs::write(
         Path::new(out_dir).join("generated_monster.rs"),
         &generated_monster,
@@ -0,0 +1,60 @@
+use anyhow::bail;
+use std::collections::HashSet;
+
+/// A message of a `.lrc` file with the languages it's translated to.
+struct Msg<'a> {
+    key: &'a str,
+    line: usize,
+    languages: Vec<&'a str>,
+}
+
+/// Checks that every message of a `.lrc` file exists in all languages of the file.
+///
+/// typed-i18n silently falls back to another language, for the UI a missing translation is an error.
+pub fn check_messages(content: &str) -> Result<(), anyhow::Error> {
+    let mut messages: Vec<Msg> = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') || line.starts_with('/') {
+            // comment
+        } else if let Some(key) = line.strip_prefix('#') {
+            messages.push(Msg {
+                key: key.trim(),
+                line: index + 1,
+                languages: Vec::new(),
+            });
+        } else if let Some(message) = messages.last_mut() {
+            let (language, _) = line.split_once(' ').unwrap_or((line, ""));
+            message.languages.push(language);
+        } else {
+            bail!("Translation without a message in line {}", index + 1);
+        }
+    }
+
+    let languages = messages
+        .iter()
+        .flat_map(|message| message.languages.iter().copied())
+        .collect::<HashSet<_>>();
+    let mut missing = Vec::new();
+    for message in &messages {
+        let mut absent = languages
+            .iter()
+            .filter(|language| !message.languages.contains(language))
+            .copied()
+            .collect::<Vec<_>>();
+        if !absent.is_empty() {
+            absent.sort_unstable();
+            missing.push(format!(
+                "{} (line {}): {}",
+                message.key,
+                message.line,
+                absent.join(", ")
+            ));
+        }
+    }
+    if !missing.is_empty() {
+        bail!("Missing translations:\n{}", missing.join("\n"));
+    }
+
+    Ok(())
+}
@@ -1,33 +1,58 @@
-use crate::game::{Color, Content};
+use crate::content::Cnt;
+use crate::csv::{Csv, CsvError, Row};
+use crate::language::{Lng, NameColumns, Names, write_names};
+use crate::slot::Color;
 use anyhow::{Context, anyhow, bail};
 use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
 use std::fs::{self, exists};
-use std::str::FromStr;
-
-pub fn monster() -> Result<(String, Vec<Mns>), anyhow::Error> {
-    let monsters = include_str!("monster.csv")
-        .lines()
-        .skip(1)
-        .map(|l| l.split(',').collect::<Vec<_>>())
-        .filter(|l| l.len() >= 5)
-        .map(monster_read)
-        .collect::<Result<Vec<_>, _>>()?;
+use std::sync::LazyLock;
 
-    monsters.iter().try_fold(HashSet::new(), |mut s, m| {
-        if !s.insert(m.name_en) {
-            bail!("Duplicate monster name: {}", m.name_en);
-        } else {
-            Ok(s)
-        }
-    })?;
+/// The columns before the translated names.
+const COLUMNS: usize = 4;
+
+static MONSTER_CSV: LazyLock<Result<Csv<'static>, CsvError>> =
+    LazyLock::new(|| Csv::parse("monster.csv", include_str!("monster.csv")));
+
+fn monster_csv() -> anyhow::Result<&'static Csv<'static>> {
+    MONSTER_CSV.as_ref().map_err(|error| anyhow!("{error}"))
+}
+
+pub(crate) fn monster_name_columns() -> anyhow::Result<NameColumns> {
+    NameColumns::parse(&monster_csv()?.header, COLUMNS)
+}
+
+pub fn monster(contents: &[Cnt], languages: &[Lng]) -> Result<(String, Vec<Mns>), anyhow::Error> {
+    let csv = monster_csv()?;
+    let name_columns = NameColumns::parse(&csv.header, COLUMNS)?;
+    let monsters = csv
+        .rows
+        .iter()
+        .map(|row| monster_read(contents, &name_columns, csv.header.len(), row))
+        .collect::<Result<Vec<_>, _>>()?;
 
+    let mut names = HashSet::new();
     let mut all_images = HashMap::new();
-    for monster in &monsters {
+    for (monster, row) in monsters.iter().zip(&csv.rows) {
+        if !names.insert(monster.name_en) {
+            bail!(row.error(1, format!("Duplicate monster name: {}", monster.name_en)));
+        }
         if let Some((src, dst)) = &monster.image
             && let Some(other_src) = all_images.insert(dst, src)
         {
-            bail!("Duplicate image: src1={other_src}, src1={src}, dst={dst}");
+            bail!(row.error(
+                1,
+                format!("Duplicate image: src1={other_src}, src2={src}, dst={dst}"),
+            ));
+        }
+    }
+    for (monster, row) in monsters.iter().zip(&csv.rows) {
+        if monster.miniature != "self"
+            && !monsters
+                .iter()
+                .any(|other| other.name_en == monster.miniature)
+        {
+            bail!(row.error(3, format!("Unknown miniature: {}", monster.miniature)));
         }
     }
 
@@ -35,19 +60,18 @@ pub fn monster() -> Result<(String, Vec<Mns>), anyhow::Error> {
 
     writeln!(
         output,
-        "#[cfg_attr(feature = \"debug\", derive(Debug, Serialize))]"
+        "#[derive(Copy, Clone, Debug, EnumTools, Eq, PartialEq, Hash, Serialize, Deserialize)]"
     )?;
-    writeln!(output, "#[derive(Copy, Clone, EnumTools, Eq, PartialEq)]")?;
     writeln!(output, "#[repr(u8)]")?;
-    writeln!(output, "#[enum_tools(iter)]")?;
+    writeln!(output, "#[enum_tools(from_str, into, iter, try_from)]")?;
     writeln!(output, "#[allow(dead_code)]")?;
-    writeln!(output, "pub(crate) enum Monster {{")?;
+    writeln!(output, "pub enum Monster {{")?;
     for monster in &monsters {
         writeln!(output, "  {},", &monster.ident)?;
     }
     writeln!(output, "}}")?;
     writeln!(output, "impl Monster {{")?;
-    writeln!(output, "    pub(crate) fn content(self) -> Content {{")?;
+    writeln!(output, "    pub fn content(self) -> Content {{")?;
     writeln!(output, "        #[allow(clippy::match_same_arms)]")?;
     writeln!(output, "        match self {{")?;
     for monster in &monsters {
@@ -59,22 +83,19 @@ pub fn monster() -> Result<(String, Vec<Mns>), anyhow::Error> {
     }
     writeln!(output, "        }}")?;
     writeln!(output, "    }}")?;
-    writeln!(output, "    pub(crate) fn color(self) -> Color {{")?;
+    writeln!(output, "    pub fn color(self) -> Color {{")?;
     writeln!(output, "        #[allow(clippy::match_same_arms)]")?;
     writeln!(output, "        match self {{")?;
     for monster in &monsters {
         writeln!(
             output,
-            "            Monster::{} => Color::{},",
+            "            Monster::{} => Color::{:?},",
             &monster.ident, &monster.color
         )?;
     }
     writeln!(output, "        }}")?;
     writeln!(output, "    }}")?;
-    writeln!(
-        output,
-        "    pub(crate) fn miniature(self) -> Option<Monster> {{"
-    )?;
+    writeln!(output, "    pub fn miniature(self) -> Option<Monster> {{")?;
     writeln!(output, "        #[allow(clippy::match_same_arms)]")?;
     writeln!(output, "        match self {{")?;
     for monster in &monsters {
@@ -91,35 +112,16 @@ pub fn monster() -> Result<(String, Vec<Mns>), anyhow::Error> {
     }
     writeln!(output, "        }}")?;
     writeln!(output, "    }}")?;
-    writeln!(
-        output,
-        "    pub(crate) fn name(self, language: GameLanguage) -> &'static str {{"
-    )?;
-    writeln!(output, "        match language {{")?;
-    writeln!(output, "            GameLanguage::En => match self {{")?;
-    for monster in &monsters {
-        writeln!(
-            output,
-            "                Monster::{} => {:?},",
-            &monster.ident, &monster.name_en
-        )?;
-    }
-    writeln!(output, "            }},")?;
-    writeln!(output, "            GameLanguage::De => match self {{")?;
-    for monster in &monsters {
-        writeln!(
-            output,
-            "                Monster::{} => {:?},",
-            &monster.ident, &monster.name_de
-        )?;
-    }
-    writeln!(output, "            }},")?;
-    writeln!(output, "        }}")?;
-    writeln!(output, "    }}")?;
-    writeln!(
-        output,
-        "    pub(crate) fn image(self) -> Option<&'static str> {{"
+    write_names(
+        &mut output,
+        "Monster",
+        &monsters
+            .iter()
+            .map(|monster| (monster.ident.as_str(), &monster.names))
+            .collect::<Vec<_>>(),
+        languages,
     )?;
+    writeln!(output, "    pub fn image(self) -> Option<&'static str> {{")?;
     writeln!(output, "        #[allow(clippy::match_same_arms)]")?;
     writeln!(output, "        match self {{")?;
     for monster in &monsters {
@@ -138,28 +140,37 @@ pub fn monster() -> Result<(String, Vec<Mns>), anyhow::Error> {
 }
 
 pub(crate) struct Mns {
-    content: Content,
+    content: &'static str,
     pub(crate) name_en: &'static str,
     pub(crate) color: Color,
     miniature: &'static str,
-    name_de: &'static str,
+    names: Names,
     pub(crate) ident: String,
     image: Option<(String, String)>,
 }
 
-fn monster_read(line: Vec<&'static str>) -> anyhow::Result<Mns> {
-    let content =
-        Content::from_str(line[0]).map_err(|_| anyhow!("Unknown content: {}", line[0]))?;
-    let name_en = line[1];
-    let color = Color::from_str(line[2]).map_err(|_| anyhow!("Unknown color: {}", line[2]))?;
-    let miniature = line[3];
-    let name_de = line[4];
+fn monster_read(
+    contents: &[Cnt],
+    name_columns: &NameColumns,
+    columns: usize,
+    row: &'static Row<'static>,
+) -> anyhow::Result<Mns> {
+    row.check_len(COLUMNS, Some(columns))?;
+    let (_, content) = Cnt::find(contents, row.field(0)).map_err(|error| row.error(0, error))?;
+    let name_en = row.field(1);
+    if name_en.is_empty() {
+        bail!(row.error(1, "Missing name"));
+    }
+    let color = Color::from_str(row.field(2))
+        .ok_or_else(|| row.error(2, format!("Unknown color: {}", row.field(2))))?;
+    let miniature = row.field(3);
+    let names = name_columns.names(name_en, row);
     let ident = name_to_ident(name_en);
 
     let image_path = format!(
         "{}/../static/miniature/{}{ident}.jpeg",
         env!("CARGO_MANIFEST_DIR"),
-        content.image_prefix(),
+        content.image_prefix,
     );
     let image = if miniature == "self" && exists(&image_path).context("stat image")? {
         let digest = md5::compute(fs::read(&image_path).context("reading image")?);
@@ -168,7 +179,7 @@ fn monster_read(line: Vec<&'static str>) -> anyhow::Result<Mns> {
         dst_filename.push_str(".jpeg");
 
         Some((
-            format!("{}{ident}.jpeg", content.image_prefix()),
+            format!("{}{ident}.jpeg", content.image_prefix),
             dst_filename,
         ))
     } else {
@@ -176,11 +187,11 @@ fn monster_read(line: Vec<&'static str>) -> anyhow::Result<Mns> {
     };
 
     Ok(Mns {
-        content,
+        content: content.ident,
         name_en,
         color,
         miniature,
-        name_de,
+        names,
         ident,
         image,
     })
@@ -193,10 +204,7 @@ fn name_to_ident(name: &str) -> String {
 pub(crate) fn monster_image(monsters: &Vec<Mns>) -> anyhow::Result<String> {
     let mut output = String::new();
 
-    writeln!(
-        &mut output,
-        "pub(crate) const MONSTER_IMAGES: &[(&str,&str)] = &["
-    )?;
+    writeln!(&mut output, "pub const MONSTER_IMAGES: &[(&str,&str)] = &[")?;
     for monster in monsters {
         if let Some((src, dst)) = &monster.image {
             writeln!(&mut output, "    ({src:?}, {dst:?}), ")?;
@@ -1,33 +1,64 @@
-use crate::game::{Color, Content, Level, Number};
+use crate::content::Cnt;
+use crate::csv::{Csv, CsvError, Row};
+use crate::language::{Lng, NameColumns, Names};
 use crate::monster::Mns;
+use crate::setup_row::{KIND_COLUMN, Kind, NAME_COLUMN, Slot, read_kind, read_slots};
 use anyhow::{anyhow, bail};
 use std::fmt::Write;
 use std::str::FromStr;
+use std::sync::LazyLock;
 
-pub fn setup(monsters: &[Mns]) -> Result<String, anyhow::Error> {
-    let setups = include_str!("setup.csv")
-        .lines()
-        .map(|l| l.trim_end_matches(',').split(',').collect::<Vec<_>>())
-        .filter(|l| l.len() >= 4)
-        .map(|l| Setup::read(monsters, &l))
+/// The columns before the translated names.
+const COLUMNS: usize = 4;
+
+static SETUP_CSV: LazyLock<Result<Csv<'static>, CsvError>> =
+    LazyLock::new(|| Csv::parse("setup.csv", include_str!("setup.csv")));
+
+fn setup_csv() -> anyhow::Result<&'static Csv<'static>> {
+    SETUP_CSV.as_ref().map_err(|error| anyhow!("{error}"))
+}
+
+pub(crate) fn setup_name_columns() -> anyhow::Result<NameColumns> {
+    NameColumns::parse(&setup_csv()?.header, COLUMNS)
+}
+
+pub fn setup(
+    contents: &[Cnt],
+    monsters: &[Mns],
+    languages: &[Lng],
+) -> Result<String, anyhow::Error> {
+    let csv = setup_csv()?;
+    // the monsters follow the named columns
+    let first_monster = csv.header.len();
+    let name_columns = NameColumns::parse(&csv.header, COLUMNS)?;
+    let setups = csv
+        .rows
+        .iter()
+        .map(|row| Setup::read(contents, monsters, &name_columns, first_monster, row))
         .collect::<Result<Vec<_>, _>>()?;
 
-    setups.iter().try_fold(None, |a: Option<&Setup>, i| {
-        if let Some(a) = a
-            && (i.content, i.chapter) < (a.content, a.chapter)
-        {
-            bail!(
-                "wrong order: {}.{}.{} is before {}.{}.{}",
-                a.content,
-                a.chapter,
-                a.name_en,
-                i.content,
-                i.chapter,
-                i.name_en
-            );
+    for (pair, rows) in setups.windows(2).zip(csv.rows.windows(2)) {
+        let (a, i) = (&pair[0], &pair[1]);
+        if (i.content_index, i.chapter) < (a.content_index, a.chapter) {
+            let name = |row: &Row| {
+                format!(
+                    "{}.{}.{} {}",
+                    row.field(0),
+                    row.field(1),
+                    row.field(KIND_COLUMN),
+                    row.field(NAME_COLUMN)
+                )
+            };
+            bail!(rows[1].error(
+                0,
+                format!(
+                    "wrong order: {} is before {}",
+                    name(&rows[0]),
+                    name(&rows[1])
+                ),
+            ));
         }
-        Ok(Some(i))
-    })?;
+    }
 
     let mut output = String::new();
 
@@ -36,18 +67,30 @@ pub fn setup(monsters: &[Mns]) -> Result<String, anyhow::Error> {
         writeln!(output, "    Setup {{")?;
         writeln!(output, "        content: Content::{},", setup.content)?;
         writeln!(output, "        chapter: Chapter({}),", setup.chapter)?;
-        writeln!(output, "        name_en: {:#?},", setup.name_en)?;
-        writeln!(output, "        name_de: {:#?},", setup.name_de)?;
+        writeln!(output, "        kind: {},", setup.kind.code())?;
+        let labels = languages
+            .iter()
+            .filter(|_| !setup.names.en.is_empty())
+            .filter_map(|language| {
+                setup
+                    .names
+                    .get(language)
+                    .map(|name| format!("(GameLanguage::{}, {name:?})", language.ident()))
+            })
+            .collect::<Vec<_>>();
+        writeln!(output, "        labels: &[{}],", labels.join(", "))?;
         writeln!(output, "        monsters: &[")?;
         for item in setup.items {
             writeln!(
                 output,
-                "            SetupItem {{ number: Number::{}, color: Color::{}, level: Level::{}, monster: {}, exclude: {:#?} }},",
+                "            SetupItem {{ number: Number::{:?}, color: Color::{:?}, level: Level::{:?}, monster: {}, exclude: {:#?}, random: None }},",
                 item.number,
                 item.color,
                 item.level,
-                item.monster
-                    .map_or("None".to_string(), |m| format!("Some(Monster::{})", m)),
+                item.monster.map_or("None".to_string(), |m| format!(
+                    "Some(Monster::{})",
+                    m.ident
+                )),
                 item.exclude,
             )?;
         }
@@ -60,125 +103,56 @@ pub fn setup(monsters: &[Mns]) -> Result<String, anyhow::Error> {
 }
 
 pub(crate) struct Setup<'a> {
-    content: Content,
+    /// position in the content list, the setups must be in that order
+    content_index: usize,
+    content: &'static str,
     chapter: usize,
-    name_en: &'static str,
-    name_de: &'static str,
-    items: Vec<SetupItem<'a>>,
+    kind: Kind,
+    /// the label, only some kinds have one
+    names: Names,
+    items: Vec<Slot<&'a Mns>>,
 }
 
-pub(crate) struct SetupItem<'a> {
-    pub(crate) number: Number,
-    pub(crate) color: Color,
-    pub(crate) level: Level,
-    pub(crate) monster: Option<&'a str>,
-    pub(crate) exclude: bool,
+impl Kind {
+    fn code(self) -> String {
+        match self {
+            Kind::Setup => "SetupKind::Setup".to_string(),
+            Kind::Door(number) => format!("SetupKind::Door({number})"),
+            Kind::NamedDoor => "SetupKind::NamedDoor".to_string(),
+            Kind::Page(page) => format!("SetupKind::Page({page})"),
+        }
+    }
 }
 
 impl<'s> Setup<'s> {
-    fn read<'a: 's>(all_monsters: &'a [Mns], fields: &[&'static str]) -> anyhow::Result<Self> {
-        let m = fields[4..]
-            .chunks(2)
-            .map(|x| (x[0], x.get(1).map_or("", |y| *y)))
-            .filter(|(x, y)| !x.is_empty() || !y.is_empty())
-            .collect::<Vec<_>>();
-        let mut monsters = Vec::with_capacity(m.len());
-        let mut last_number = Number::One;
-        for (f1, f2) in m {
-            let error = |reason: &str| -> anyhow::Error {
-                anyhow!(
-                    "error \"{reason}\" on field \"{f1}\", \"{f2}\" in line \"{}\"",
-                    fields.join(",")
-                )
-            };
-
-            let (number, mut color, level, exclude) = if f1 == "Exclude" {
-                (Number::One, Color::White, Level::Rookie, true)
-            } else {
-                let (f1_co_num, f1_le) = f1.split_once(' ').unwrap_or((f1, ""));
-                if f1_co_num.len() < 2 {
-                    return Err(error("field too short"));
-                }
-                let co = Color::iter()
-                    .find(|c| c.as_str().starts_with(&f1_co_num[0..1]))
-                    .ok_or_else(|| error("unknown color"))?;
-
-                let nu = u8::from_str(&f1_co_num[1..2])
-                    .map_err(|_| ())
-                    .and_then(Number::try_from)
-                    .map_err(|_| error("unknown number"))?;
-
-                let le = match co {
-                    Color::Commander | Color::Special | Color::SpecialCommander => {
-                        if f1_le.is_empty() {
-                            Level::Rookie
-                        } else {
-                            return Err(error("unknown commander/special level"));
-                        }
-                    }
-                    _ => Level::iter()
-                        .find(|level| !f1_le.is_empty() && level.as_str().starts_with(f1_le))
-                        .ok_or_else(|| error("unknown regular level"))?,
-                };
-
-                if nu < last_number {
-                    return Err(error("number decreased"));
-                }
-                last_number = nu;
-
-                (nu, co, le, false)
-            };
-
-            let monster = if f2.is_empty() {
-                if color.is_any_special() {
-                    return Err(error("special without monster"));
-                }
-                None
-            } else {
-                let (sp, f2) = f2
-                    .strip_prefix('*')
-                    .map(|f2| (true, f2))
-                    .unwrap_or((false, f2));
-
-                let monster = all_monsters
-                    .iter()
-                    .find(|m| m.name_en == f2)
-                    .ok_or_else(|| error("unknown monster"))?;
-
-                if !(exclude
-                    || (!sp && !color.is_any_special() && color == monster.color)
-                    || (sp && color.is_any_special() && monster.color.is_any_special()))
-                {
-                    return Err(error(&format!(
-                        "color mismatch sp={:#?}, monster-color:{}",
-                        sp, monster.color,
-                    )));
-                }
-
-                if color.is_any_special() && monster.color == Color::SpecialCommander {
-                    color = Color::SpecialCommander;
-                }
-
-                Some(monster.ident.as_str())
-            };
-
-            monsters.push(SetupItem {
-                number,
-                color,
-                level,
-                monster,
-                exclude,
-            });
-        }
+    fn read<'a: 's>(
+        contents: &[Cnt],
+        all_monsters: &'a [Mns],
+        name_columns: &NameColumns,
+        first_monster: usize,
+        row: &'static Row<'static>,
+    ) -> anyhow::Result<Self> {
+        row.check_len(first_monster, None)?;
+        let (content_index, content) =
+            Cnt::find(contents, row.field(0)).map_err(|error| row.error(0, error))?;
+        let chapter = usize::from_str(row.field(1))
+            .map_err(|_| row.error(1, format!("unknown chapter: {}", row.field(1))))?;
+        let names = name_columns.names(row.field(NAME_COLUMN), row);
+        let kind = read_kind(row, !names.is_empty())?;
+        let items = read_slots(row, first_monster, |name| {
+            all_monsters
+                .iter()
+                .find(|monster| monster.name_en == name)
+                .map(|monster| (monster, monster.color))
+        })?;
 
         Ok(Self {
-            content: Content::from_str(fields[0])
-                .map_err(|_| anyhow!("unknown content on \"{}\"", fields.join(",")))?,
-            chapter: usize::from_str(fields[1])
-                .map_err(|_| anyhow!("unknown chapter on \"{}\"", fields.join(",")))?,
-            name_en: fields[2],
-            name_de: fields[3],
-            items: monsters,
+            content_index,
+            content: content.ident,
+            chapter,
+            kind,
+            names,
+            items,
         })
     }
 }
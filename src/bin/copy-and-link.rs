@@ -1,3 +1,4 @@
+use engine::MONSTER_IMAGES;
 use std::fs;
 use std::path::{Path, PathBuf};
 use yew_bootstrap::icons::BIFiles;
@@ -58,13 +59,9 @@ fn copy_miniatures(staging_dir_path: &Path) -> Result<(), std::io::Error> {
         std::env::var("TRUNK_SOURCE_DIR").expect("Environment variable TRUNK_SOURCE_DIR");
     let input_path = Path::new(&source_dir_path).join("static").join("miniature");
 
-    for (src, dst) in generated::MONSTER_IMAGES {
+    for (src, dst) in MONSTER_IMAGES {
         fs::copy(input_path.join(src), output_path.join(dst))?;
     }
 
     Ok(())
 }
-
-mod generated {
-    include!(concat!(env!("OUT_DIR"), "/generated_monster_image.rs"));
-}
@@ -0,0 +1,360 @@
+use crate::msg::MsgLanguage;
+use engine::game::{AnyMonster, Chapter, Color, Content, GameLanguage, Level, Number, SETUPS};
+use engine::roll::{Incomplete, Item, Missing, RollSettings, roll};
+use engine::setup::{RandomSlot, SetupItem, SetupKind};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng, rng};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::process::ExitCode;
+
+// only the messages of a roll are used
+#[allow(dead_code)]
+#[path = "../msg.rs"]
+mod msg;
+
+const USAGE: &str = "\
+Usage: roll [options] --preset <content> <chapter> <name>
+       roll [options] <slot>...
+
+A slot is a color with a number followed by a level, e.g. `W1 Ro G2 Fi`,
+commanders have no level, e.g. `C1`. The color `?` is any regular color and
+a level range is drawn as well, e.g. `?1 Fi G2 Ro-Ve`.
+
+Options:
+  --content <content,...>  the enabled content (default: Core)
+  --players <1-5>          the number of players (default: 5)
+  --language <language>    the language of the names, e.g. de or German (default: en)
+  --seed <seed>            the seed, the same as in the app (default: random)
+  --json                   print the roll as JSON
+  --help                   print this help
+";
+
+const LEVELS: [Level; 4] = [
+    Level::Rookie,
+    Level::Fighter,
+    Level::Veteran,
+    Level::Champion,
+];
+
+struct Args {
+    content: HashSet<Content>,
+    players: Number,
+    language: GameLanguage,
+    /// index into `SETUPS`
+    setup: Option<usize>,
+    slots: Vec<SetupItem>,
+    seed: Option<u32>,
+    json: bool,
+}
+
+#[derive(Serialize)]
+struct JsonRoll<'a> {
+    setup: Option<JsonSetup>,
+    seed: u32,
+    complete: bool,
+    monsters: Vec<JsonItem<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonSetup {
+    content: Content,
+    chapter: Chapter,
+    kind: SetupKind,
+    name: String,
+}
+
+#[derive(Serialize)]
+struct JsonItem<'a> {
+    #[serde(flatten)]
+    item: &'a Item,
+    name: Option<&'a str>,
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1).collect()) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("{error}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let selected = match args.setup {
+        Some(index) => SETUPS[index].monsters.to_vec(),
+        None => args.slots.clone(),
+    };
+    let seed = args.seed.unwrap_or_else(|| rng().random());
+    let settings = RollSettings {
+        content: args.content.clone(),
+        players: Some(args.players),
+        ..RollSettings::default()
+    };
+    let (output, complete) = match roll(
+        &settings,
+        &selected,
+        &mut StdRng::seed_from_u64(u64::from(seed)),
+    ) {
+        Ok(output) => (output, true),
+        Err(Incomplete(output)) => (output, false),
+    };
+    let output = output
+        .iter()
+        .filter(|item| item.number <= args.players)
+        .collect::<Vec<_>>();
+
+    if args.json {
+        let json = JsonRoll {
+            setup: args.setup.map(|index| JsonSetup {
+                content: SETUPS[index].content,
+                chapter: SETUPS[index].chapter,
+                kind: SETUPS[index].kind,
+                name: SETUPS[index].name(args.language),
+            }),
+            seed,
+            complete,
+            monsters: output
+                .iter()
+                .map(|item| JsonItem {
+                    item,
+                    name: item.monster.map(|monster| monster.name(args.language, &[])),
+                })
+                .collect(),
+        };
+        match serde_json::to_string_pretty(&json) {
+            Ok(json) => println!("{json}"),
+            Err(error) => {
+                eprintln!("{error}");
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        print_text(&args, seed, &output);
+    }
+
+    if complete {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn print_text(args: &Args, seed: u32, output: &[&Item]) {
+    let language = args.language;
+    if let Some(setup) = args.setup.map(|index| &SETUPS[index]) {
+        println!(
+            "{} - {} - {}",
+            setup.content.name(language),
+            setup.chapter.0,
+            setup.name(language)
+        );
+    }
+    println!("Seed: {seed}");
+    for item in output {
+        let mut line = format!("{} ", item.number.as_str());
+        if !item.color.is_any_special() {
+            line.push_str(item.color.short(language));
+            line.push_str(" - ");
+        }
+        match item.monster {
+            Some(monster) => {
+                line.push_str(monster.name(language, &[]));
+                if item.preset {
+                    line.push('*');
+                }
+            }
+            None => line.push('!'),
+        }
+        if !item.color.is_any_commander() && !item.color.is_any_special() {
+            line.push_str(" - ");
+            line.push_str(item.level.name(language));
+        }
+        if let Some(AnyMonster::Game(monster)) = item.monster
+            && let Some(miniature) = monster.miniature()
+        {
+            line.push_str(" - ");
+            line.push_str(miniature.name(language));
+        }
+        if let Some(missing) = item.missing {
+            // English if the messages are not translated
+            let msg = MsgLanguage::iter()
+                .find(|msg| msg.code() == language.code())
+                .unwrap_or_default();
+            let color = item.color.name(language);
+            let reason = match missing {
+                Missing::NotEnough => msg.missing_not_enough(color),
+                Missing::Banned => msg.missing_banned(color),
+                Missing::Miniature => msg.missing_miniature(color),
+            };
+            line.push_str(&format!(" ({reason})"));
+        }
+        println!("{line}");
+    }
+}
+
+/// Parses the arguments, `None` if the help is requested.
+fn parse_args(args: Vec<String>) -> Result<Option<Args>, String> {
+    let mut result = Args {
+        content: HashSet::from([Content::Core]),
+        players: Number::Five,
+        language: GameLanguage::En,
+        setup: None,
+        slots: Vec::new(),
+        seed: None,
+        json: false,
+    };
+    let mut preset = None;
+    let mut slots = Vec::new();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("Missing value for {name}"));
+        match arg.as_str() {
+            "--help" | "-h" => return Ok(None),
+            "--json" => result.json = true,
+            "--content" => {
+                result.content = value("--content")?
+                    .split(',')
+                    .map(parse_content)
+                    .collect::<Result<_, _>>()?;
+            }
+            "--players" => {
+                let players = value("--players")?;
+                result.players = players
+                    .parse()
+                    .ok()
+                    .and_then(Number::try_from)
+                    .ok_or(format!("Unknown number of players: {players}"))?;
+            }
+            "--language" => {
+                let language = value("--language")?;
+                // either the code or the name
+                result.language = GameLanguage::iter()
+                    .find(|l| {
+                        language.eq_ignore_ascii_case(l.code())
+                            || language.eq_ignore_ascii_case(l.as_str())
+                    })
+                    .ok_or(format!("Unknown language: {language}"))?;
+            }
+            "--seed" => {
+                let seed = value("--seed")?;
+                result.seed = Some(seed.parse().map_err(|_| format!("Invalid seed: {seed}"))?);
+            }
+            "--preset" => {
+                let content = value("--preset")?;
+                let chapter = value("--preset")?;
+                let name = value("--preset")?;
+                preset = Some((content, chapter, name));
+            }
+            _ if arg.starts_with("--") => return Err(format!("Unknown option: {arg}")),
+            _ => slots.push(arg),
+        }
+    }
+
+    match preset {
+        Some((content, chapter, name)) => {
+            if !slots.is_empty() {
+                return Err("Either --preset or slots can be used".to_string());
+            }
+            result.setup = Some(find_setup(&content, &chapter, &name)?);
+        }
+        None if slots.is_empty() => return Err("Neither --preset nor slots given".to_string()),
+        None => result.slots = parse_slots(&slots)?,
+    }
+
+    Ok(Some(result))
+}
+
+/// Finds a content by its identifier or by its name in any language.
+fn parse_content(content: &str) -> Result<Content, String> {
+    let content = content.trim();
+    Content::iter()
+        .find(|c| {
+            content.eq_ignore_ascii_case(c.as_str())
+                || GameLanguage::iter().any(|l| content.eq_ignore_ascii_case(c.name(l)))
+        })
+        .ok_or(format!("Unknown content: {content}"))
+}
+
+/// Finds a setup by its content, chapter and name (in any language), returns the index into `SETUPS`.
+fn find_setup(content: &str, chapter: &str, name: &str) -> Result<usize, String> {
+    let content = parse_content(content)?;
+    let chapter = chapter
+        .parse()
+        .map(Chapter)
+        .map_err(|_| format!("Invalid chapter: {chapter}"))?;
+    SETUPS
+        .iter()
+        .position(|setup| {
+            setup.content == content
+                && setup.chapter == chapter
+                && GameLanguage::iter().any(|l| name.eq_ignore_ascii_case(&setup.name(l)))
+        })
+        .ok_or(format!(
+            "Unknown setup: {} {} {name}",
+            content.as_str(),
+            chapter.0
+        ))
+}
+
+/// Parses slots like `W1 Ro G2 Fi C3 ?4 Ro-Ve`, the colors and levels may be in any language.
+fn parse_slots(slots: &[String]) -> Result<Vec<SetupItem>, String> {
+    let mut result = Vec::new();
+    let mut slots = slots.iter();
+    while let Some(slot) = slots.next() {
+        let (prefix, number) = slot.split_at_checked(1).ok_or("Empty slot")?;
+        let any_color = prefix == "?";
+        let color = if any_color {
+            Color::White
+        } else {
+            Color::iter()
+                .filter(|color| !color.is_any_special())
+                .find(|color| {
+                    GameLanguage::iter().any(|l| prefix.eq_ignore_ascii_case(color.prefix(l)))
+                })
+                .ok_or(format!("Unknown color: {slot}"))?
+        };
+        let number = number
+            .parse()
+            .ok()
+            .and_then(Number::try_from)
+            .ok_or(format!("Unknown number: {slot}"))?;
+        let (level, max_level) = if color.is_any_commander() {
+            (Level::Rookie, Level::Rookie)
+        } else {
+            let levels = slots.next().ok_or(format!("Missing level after {slot}"))?;
+            let (level, max_level) = levels.split_once('-').unwrap_or((levels, levels));
+            let (level, max_level) = (parse_level(level)?, parse_level(max_level)?);
+            if max_level < level {
+                return Err(format!("Invalid level range: {levels}"));
+            }
+            (level, max_level)
+        };
+        result.push(SetupItem {
+            number,
+            color,
+            level,
+            monster: None,
+            exclude: false,
+            random: (any_color || max_level > level).then_some(RandomSlot {
+                any_color,
+                max_level,
+            }),
+        });
+    }
+    Ok(result)
+}
+
+fn parse_level(level: &str) -> Result<Level, String> {
+    LEVELS
+        .into_iter()
+        .find(|l| {
+            level.eq_ignore_ascii_case(l.id())
+                || GameLanguage::iter().any(|gl| level.eq_ignore_ascii_case(l.name(gl)))
+        })
+        .ok_or(format!("Unknown level: {level}"))
+}
@@ -0,0 +1,268 @@
+//! Setups imported by the user, they are shown as an extra content with the preset setups.
+
+use crate::Settings;
+use crate::select::{ForgetCustom, SelectStore};
+use engine::game::{Chapter, Content, GameLanguage, SETUPS};
+use engine::setup::{CsvError, CustomSetup, Setup, SetupItem, SetupKey, SetupKind, export, import};
+use serde::{Deserialize, Serialize};
+use std::rc::Rc;
+use web_sys::HtmlTextAreaElement;
+use yew::{
+    Callback, Html, InputEvent, TargetCast, function_component, html, use_effect_with, use_state,
+};
+use yew_bootstrap::component::{Alert, Button, ButtonSize};
+use yew_bootstrap::util::Color;
+use yewdux::{Dispatch, Reducer, Store, use_store};
+
+/// The imported setups and the text they were imported from.
+#[derive(Clone, Default, PartialEq, Deserialize, Serialize, Store)]
+#[store(storage = "local", storage_tab_sync)]
+#[serde(default)]
+pub(crate) struct CustomSetups {
+    text: String,
+    pub(crate) setups: Vec<CustomSetup>,
+}
+
+impl CustomSetups {
+    /// The chapter after the last one, setups can only be added in order.
+    pub(crate) fn next_chapter(&self) -> Chapter {
+        Chapter(self.setups.last().map_or(1, |setup| setup.chapter.0 + 1))
+    }
+}
+
+/// A setup which can be shown, either a preset or an imported one.
+#[cfg_attr(feature = "debug", derive(Debug))]
+#[derive(Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "StoredSetupId", into = "StoredSetupId")]
+pub(crate) enum SetupId {
+    /// index into `SETUPS`, it's stored as the key of the setup
+    Preset(usize),
+    /// index into `CustomSetups::setups`
+    Custom { custom: usize },
+}
+
+/// How a [`SetupId`] is stored, the indices into `SETUPS` change when setups are added.
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum StoredSetupId {
+    Preset(SetupKey),
+    Custom {
+        custom: usize,
+    },
+    /// an index into `SETUPS` of an earlier version, which may be another setup by now
+    Index(usize),
+}
+
+impl From<StoredSetupId> for SetupId {
+    fn from(stored: StoredSetupId) -> Self {
+        match stored {
+            // an unknown setup doesn't exist, see `SetupId::exists`
+            StoredSetupId::Preset(key) => SetupId::Preset(key.index().unwrap_or(SETUPS.len())),
+            StoredSetupId::Custom { custom } => SetupId::Custom { custom },
+            StoredSetupId::Index(_) => SetupId::Preset(SETUPS.len()),
+        }
+    }
+}
+
+impl From<SetupId> for StoredSetupId {
+    fn from(setup: SetupId) -> Self {
+        match setup.key() {
+            Some(key) => StoredSetupId::Preset(key),
+            None => match setup {
+                SetupId::Preset(index) => StoredSetupId::Index(index),
+                SetupId::Custom { custom } => StoredSetupId::Custom { custom },
+            },
+        }
+    }
+}
+
+impl SetupId {
+    /// The index into `SETUPS`, `None` for an imported setup.
+    pub(crate) fn preset(self) -> Option<usize> {
+        match self {
+            SetupId::Preset(index) => Some(index),
+            SetupId::Custom { .. } => None,
+        }
+    }
+
+    /// The key of the preset setup, `None` for an imported setup.
+    pub(crate) fn key(self) -> Option<SetupKey> {
+        self.preset()
+            .and_then(|index| SETUPS.get(index))
+            .map(Setup::key)
+    }
+
+    pub(crate) fn exists(self) -> bool {
+        match self {
+            SetupId::Preset(index) => index < SETUPS.len(),
+            SetupId::Custom { custom } => {
+                custom < Dispatch::<CustomSetups>::global().get().setups.len()
+            }
+        }
+    }
+
+    /// The content (`None` for imported setups) and chapter.
+    pub(crate) fn chapter(self) -> Option<(Option<Content>, Chapter)> {
+        match self {
+            SetupId::Preset(index) => SETUPS.get(index).map(|s| (Some(s.content), s.chapter)),
+            SetupId::Custom { custom } => Dispatch::<CustomSetups>::global()
+                .get()
+                .setups
+                .get(custom)
+                .map(|s| (None, s.chapter)),
+        }
+    }
+
+    pub(crate) fn kind(self) -> Option<SetupKind> {
+        match self {
+            SetupId::Preset(index) => SETUPS.get(index).map(|s| s.kind),
+            SetupId::Custom { custom } => Dispatch::<CustomSetups>::global()
+                .get()
+                .setups
+                .get(custom)
+                .map(|s| s.kind),
+        }
+    }
+
+    pub(crate) fn name(self, game_language: GameLanguage) -> String {
+        match self {
+            SetupId::Preset(index) => SETUPS
+                .get(index)
+                .map(|s| s.name(game_language))
+                .unwrap_or_default(),
+            SetupId::Custom { custom } => Dispatch::<CustomSetups>::global()
+                .get()
+                .setups
+                .get(custom)
+                .map(|s| s.name(game_language))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The content, chapter and name.
+    pub(crate) fn title(self, settings: &Settings) -> String {
+        let Some((content, chapter)) = self.chapter() else {
+            return String::new();
+        };
+        let content =
+            content.map_or_else(|| settings.msg.custom(), |c| c.name(settings.game_language));
+        format!(
+            "{content} - {} - {}",
+            chapter.0,
+            self.name(settings.game_language)
+        )
+    }
+
+    pub(crate) fn monsters(self) -> Vec<SetupItem> {
+        match self {
+            SetupId::Preset(index) => SETUPS
+                .get(index)
+                .map(|s| s.monsters.to_vec())
+                .unwrap_or_default(),
+            SetupId::Custom { custom } => Dispatch::<CustomSetups>::global()
+                .get()
+                .setups
+                .get(custom)
+                .map(|s| s.monsters.clone())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+struct Import(String, Vec<CustomSetup>);
+impl Reducer<CustomSetups> for Import {
+    fn apply(self, _: Rc<CustomSetups>) -> Rc<CustomSetups> {
+        Dispatch::<SelectStore>::global().apply(ForgetCustom);
+        Rc::new(CustomSetups {
+            text: self.0,
+            setups: self.1,
+        })
+    }
+}
+
+/// Adds setups (e.g. generated ones) after the other ones, the text becomes the export of all.
+pub(crate) struct AddSetups(pub(crate) Vec<CustomSetup>);
+impl Reducer<CustomSetups> for AddSetups {
+    fn apply(self, mut rc_custom: Rc<CustomSetups>) -> Rc<CustomSetups> {
+        let custom = Rc::make_mut(&mut rc_custom);
+        custom.setups.extend(self.0);
+        custom.text = export(&custom.setups);
+        rc_custom
+    }
+}
+
+struct RemoveCustom;
+impl Reducer<CustomSetups> for RemoveCustom {
+    fn apply(self, _: Rc<CustomSetups>) -> Rc<CustomSetups> {
+        Dispatch::<SelectStore>::global().apply(ForgetCustom);
+        Rc::default()
+    }
+}
+
+#[function_component]
+pub(crate) fn CustomImport() -> Html {
+    let (settings, _) = use_store::<Settings>();
+    let (custom, dispatch) = use_store::<CustomSetups>();
+    let text = use_state(|| custom.text.clone());
+    let error = use_state(|| None::<CsvError>);
+    {
+        // the text changes when setups are added
+        let text = text.clone();
+        use_effect_with(custom.text.clone(), move |custom_text| {
+            text.set(custom_text.clone());
+        });
+    }
+
+    let oninput = {
+        let text = text.clone();
+        Callback::from(move |e: InputEvent| {
+            let input = e.target_unchecked_into::<HtmlTextAreaElement>();
+            text.set(input.value());
+        })
+    };
+    let onimport = {
+        let (text, error, dispatch) = (text.clone(), error.clone(), dispatch.clone());
+        Callback::from(move |_| match import(&text) {
+            Ok(setups) => {
+                error.set(None);
+                dispatch.apply(Import((*text).clone(), setups));
+            }
+            Err(err) => error.set(Some(err)),
+        })
+    };
+    let onremove = {
+        let (text, error) = (text.clone(), error.clone());
+        Callback::from(move |_| {
+            text.set(String::new());
+            error.set(None);
+            dispatch.apply(RemoveCustom);
+        })
+    };
+
+    html! {
+        <details>
+            <summary>{settings.msg.custom_setups()}{format!(" ({})", custom.setups.len())}</summary>
+            <div class="form-text">{settings.msg.custom_setups_help()}</div>
+            <textarea
+                class="form-control font-monospace mb-2"
+                rows="8"
+                value={(*text).clone()}
+                oninput={oninput}
+            />
+            if let Some(error) = &*error {
+                <Alert style={Color::Danger}>
+                    {settings.msg.import_error(
+                        &error.line.to_string(),
+                        &error.column.to_string(),
+                        &error.message,
+                    )}
+                </Alert>
+            }
+            <Button style={Color::Primary} size={ButtonSize::Small} onclick={onimport}>{settings.msg.import()}</Button>
+            {" "}
+            if !custom.setups.is_empty() {
+                <Button style={Color::Danger} outline={true} size={ButtonSize::Small} onclick={onremove}>{settings.msg.remove()}</Button>
+            }
+        </details>
+    }
+}
@@ -0,0 +1,203 @@
+//! Generated setups, which follow the patterns of the ones of the game.
+
+use crate::Settings;
+use crate::custom::{AddSetups, CustomSetups};
+use crate::select::{SelectStore, SetSelected};
+use engine::game::{Chapter, Color};
+use engine::generate::{adventure, door, setup_threat};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng, rng};
+use web_sys::HtmlInputElement;
+use yew::{Callback, Event, Html, TargetCast, UseStateHandle, function_component, html, use_state};
+use yew_bootstrap::component::{Button, ButtonSize};
+use yew_bootstrap::icons::BI;
+use yewdux::{Dispatch, use_store};
+
+/// Sets the state to the number of the input, if it is one.
+fn change_number<T: core::str::FromStr + 'static>(state: &UseStateHandle<T>) -> Callback<Event> {
+    let state = state.clone();
+    Callback::from(move |e: Event| {
+        let input = e.target_unchecked_into::<HtmlInputElement>();
+        if let Ok(value) = input.value().parse() {
+            state.set(value);
+        }
+    })
+}
+
+/// Generates a door for the custom setup, which can be edited afterwards.
+#[function_component]
+pub(crate) fn DoorGenerator() -> Html {
+    let (settings, _) = use_store::<Settings>();
+    let (store, dispatch) = use_store::<SelectStore>();
+    let threat = use_state(|| 10u32);
+    let chapter = use_state(|| 1usize);
+    let commander = use_state(|| false);
+
+    let change_threat = change_number(&threat);
+    let change_chapter = {
+        let chapter = chapter.clone();
+        Callback::from(move |e: Event| {
+            let input = e.target_unchecked_into::<HtmlInputElement>();
+            if let Ok(value) = input.value().parse::<usize>() {
+                chapter.set(value.max(1));
+            }
+        })
+    };
+    let toggle_commander = {
+        let commander = commander.clone();
+        Callback::from(move |_| commander.set(!*commander))
+    };
+    let generate = {
+        let (threat, chapter, commander) = (threat.clone(), chapter.clone(), commander.clone());
+        let players = settings.players;
+        dispatch.apply_callback(move |_| {
+            SetSelected(door(
+                players,
+                *threat,
+                Chapter(*chapter),
+                *commander,
+                &mut rng(),
+            ))
+        })
+    };
+
+    html! {
+        <div class="mt-3">
+            <div class="input-group mb-1" style="width: auto">
+                <span class="input-group-text">{settings.msg.threat()}</span>
+                <input
+                    type="number"
+                    class="form-control"
+                    min="0"
+                    style="max-width: 6em"
+                    value={threat.to_string()}
+                    onchange={change_threat}
+                />
+                <span class="input-group-text">{settings.msg.chapter()}</span>
+                <input
+                    type="number"
+                    class="form-control"
+                    min="1"
+                    style="max-width: 6em"
+                    value={chapter.to_string()}
+                    onchange={change_chapter}
+                />
+                <div class="input-group-text">
+                    <input
+                        type="checkbox"
+                        class="form-check-input mt-0 me-1"
+                        id="generate_commander"
+                        checked={*commander}
+                        onchange={toggle_commander}
+                    />
+                    <label for="generate_commander">{Color::Commander.name(settings.game_language)}</label>
+                </div>
+                <Button style={yew_bootstrap::util::Color::Primary} size={ButtonSize::Small} onclick={generate}>
+                    {settings.msg.generate_door()}
+                </Button>
+            </div>
+            <div class="form-text">
+                {settings.msg.threat_help(&setup_threat(&store.selected.borrow(), settings.players).to_string())}
+            </div>
+        </div>
+    }
+}
+
+/// Generates a chapter with a setup and doors, it's added to the imported setups.
+#[function_component]
+pub(crate) fn AdventureGenerator() -> Html {
+    let (settings, _) = use_store::<Settings>();
+    let (custom, dispatch) = use_store::<CustomSetups>();
+    let threat = use_state(|| 8u32);
+    let doors = use_state(|| 3u8);
+    let commander = use_state(|| true);
+    let seed = use_state(|| rng().random::<u32>());
+
+    let toggle_commander = {
+        let commander = commander.clone();
+        Callback::from(move |_| commander.set(!*commander))
+    };
+    let randomize = {
+        let seed = seed.clone();
+        Callback::from(move |_| seed.set(rng().random()))
+    };
+    let generate = {
+        let (threat, doors, commander, seed) = (
+            threat.clone(),
+            doors.clone(),
+            commander.clone(),
+            seed.clone(),
+        );
+        let (players, chapter) = (settings.players, custom.next_chapter());
+        Callback::from(move |_| {
+            let setups = adventure(
+                players,
+                *threat,
+                chapter,
+                (*doors).min(9),
+                *commander,
+                &mut StdRng::seed_from_u64(u64::from(*seed)),
+            );
+            dispatch.apply(AddSetups(setups));
+            // show the new chapter with the preset setups
+            Dispatch::<Settings>::global().reduce_mut(|settings| {
+                settings.preset = true;
+                settings.preset_custom = true;
+                settings.preset_chapter = chapter;
+            });
+        })
+    };
+
+    html! {
+        <details>
+            <summary>{settings.msg.generate_adventure()}</summary>
+            <div class="form-text">{settings.msg.adventure_help()}</div>
+            <div class="input-group mb-1" style="width: auto">
+                <span class="input-group-text">{settings.msg.threat()}</span>
+                <input
+                    type="number"
+                    class="form-control"
+                    min="0"
+                    style="max-width: 6em"
+                    value={threat.to_string()}
+                    onchange={change_number(&threat)}
+                />
+                <span class="input-group-text">{settings.msg.doors()}</span>
+                <input
+                    type="number"
+                    class="form-control"
+                    min="0"
+                    max="9"
+                    style="max-width: 6em"
+                    value={doors.to_string()}
+                    onchange={change_number(&doors)}
+                />
+                <div class="input-group-text">
+                    <input
+                        type="checkbox"
+                        class="form-check-input mt-0 me-1"
+                        id="adventure_commander"
+                        checked={*commander}
+                        onchange={toggle_commander}
+                    />
+                    <label for="adventure_commander">{Color::Commander.name(settings.game_language)}</label>
+                </div>
+            </div>
+            <div class="input-group mb-1" style="width: auto">
+                <span class="input-group-text">{settings.msg.seed()}</span>
+                <input
+                    type="number"
+                    class="form-control"
+                    min="0"
+                    style="max-width: 10em"
+                    value={seed.to_string()}
+                    onchange={change_number(&seed)}
+                />
+                <Button style={yew_bootstrap::util::Color::Primary} outline={true} onclick={randomize}>{BI::ARROW_COUNTERCLOCKWISE}</Button>
+                <Button style={yew_bootstrap::util::Color::Primary} size={ButtonSize::Small} onclick={generate}>
+                    {settings.msg.generate_adventure_chapter(&custom.next_chapter().0.to_string())}
+                </Button>
+            </div>
+        </details>
+    }
+}
@@ -0,0 +1,242 @@
+use crate::Settings;
+use crate::homebrew::HomebrewStore;
+use engine::game::{AnyMonster, Chapter, Content, SETUPS};
+use engine::setup::SetupKey;
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashSet;
+use std::rc::Rc;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::{Callback, Event, Html, TargetCast, function_component, html};
+use yew_bootstrap::component::{Button, ButtonSize};
+use yew_bootstrap::util::Color;
+use yewdux::{Reducer, Store, use_store};
+
+/// The maximum number of stored rolls.
+const MAX_ROLLS: usize = 50;
+
+/// The monsters of the previous rolls, oldest first.
+#[derive(Clone, Default, PartialEq, Deserialize, Serialize, Store)]
+#[store(storage = "local", storage_tab_sync)]
+#[serde(default)]
+pub(crate) struct History {
+    rolls: Vec<Roll>,
+}
+
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+struct Roll {
+    /// the preset setup, `None` for custom setups
+    #[serde(deserialize_with = "deserialize_setup")]
+    setup: Option<SetupKey>,
+    /// the content (`None` for imported setups) and chapter, `None` for custom setups
+    #[serde(default)]
+    chapter: Option<(Option<Content>, Chapter)>,
+    #[serde(deserialize_with = "engine::game::deserialize_monster_set")]
+    monsters: HashSet<AnyMonster>,
+}
+
+impl History {
+    /// Records a roll, a roll of the same setup as the last one replaces it.
+    pub(crate) fn record(
+        &mut self,
+        setup: Option<SetupKey>,
+        chapter: Option<(Option<Content>, Chapter)>,
+        monsters: HashSet<AnyMonster>,
+    ) {
+        if self.rolls.last().is_some_and(|roll| roll.setup == setup) {
+            self.rolls.pop();
+        }
+        self.rolls.push(Roll {
+            setup,
+            chapter,
+            monsters,
+        });
+        if self.rolls.len() > MAX_ROLLS {
+            self.rolls.drain(..self.rolls.len() - MAX_ROLLS);
+        }
+    }
+
+    /// Removes a monster from the rolls, e.g. a removed homebrew monster.
+    pub(crate) fn forget(&mut self, monster: AnyMonster) {
+        for roll in &mut self.rolls {
+            roll.monsters.remove(&monster);
+        }
+    }
+
+    /// The monsters of the last `count` rolls resp. chapters (if `chapters`), without the roll which
+    /// would be replaced by `setup`. Each roll of a custom setup is a chapter of its own.
+    pub(crate) fn recent(
+        &self,
+        setup: Option<&SetupKey>,
+        count: usize,
+        chapters: bool,
+    ) -> HashSet<AnyMonster> {
+        let mut previous = self.rolls.as_slice();
+        if let Some((last, rest)) = previous.split_last()
+            && last.setup.as_ref() == setup
+        {
+            previous = rest;
+        }
+        let mut taken = 0;
+        let mut chapter = None;
+        previous
+            .iter()
+            .rev()
+            .take_while(|roll| {
+                if !chapters || roll.chapter.is_none() || roll.chapter != chapter {
+                    taken += 1;
+                    chapter = roll.chapter;
+                }
+                taken <= count
+            })
+            .flat_map(|roll| roll.monsters.iter().copied())
+            .collect()
+    }
+}
+
+/// Deserializes the setup of a roll, the indices into `SETUPS` of earlier versions are dropped.
+fn deserialize_setup<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<SetupKey>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Stored {
+        Key(SetupKey),
+        Other(IgnoredAny),
+    }
+    Ok(match Option::<Stored>::deserialize(deserializer)? {
+        Some(Stored::Key(key)) => Some(key),
+        Some(Stored::Other(_)) | None => None,
+    })
+}
+
+struct ClearHistory;
+impl Reducer<History> for ClearHistory {
+    fn apply(self, _: Rc<History>) -> Rc<History> {
+        Rc::default()
+    }
+}
+
+struct ToggleFreshness;
+impl Reducer<Settings> for ToggleFreshness {
+    fn apply(self, mut rc_settings: Rc<Settings>) -> Rc<Settings> {
+        let settings = Rc::make_mut(&mut rc_settings);
+        settings.freshness = !settings.freshness;
+        rc_settings
+    }
+}
+
+struct FreshnessChapters(bool);
+impl Reducer<Settings> for FreshnessChapters {
+    fn apply(self, mut rc_settings: Rc<Settings>) -> Rc<Settings> {
+        let settings = Rc::make_mut(&mut rc_settings);
+        settings.freshness_chapters = self.0;
+        rc_settings
+    }
+}
+
+struct FreshnessRolls(usize);
+impl Reducer<Settings> for FreshnessRolls {
+    fn apply(self, mut rc_settings: Rc<Settings>) -> Rc<Settings> {
+        let settings = Rc::make_mut(&mut rc_settings);
+        settings.freshness_rolls = self.0;
+        rc_settings
+    }
+}
+
+#[function_component]
+pub(crate) fn Freshness() -> Html {
+    let (settings, dispatch) = use_store::<Settings>();
+    let (history, history_dispatch) = use_store::<History>();
+    let (homebrew, _) = use_store::<HomebrewStore>();
+
+    let toggle_freshness = dispatch.apply_callback(|_| ToggleFreshness);
+    let change_rolls = {
+        let dispatch = dispatch.clone();
+        Callback::from(move |e: Event| {
+            let input = e.target_unchecked_into::<HtmlInputElement>();
+            if let Ok(rolls) = input.value().trim().parse() {
+                dispatch.apply(FreshnessRolls(rolls));
+            }
+        })
+    };
+    let change_unit = dispatch.apply_callback(|e: Event| {
+        let select = e.target_unchecked_into::<HtmlSelectElement>();
+        FreshnessChapters(select.value() == "chapters")
+    });
+    let clear = history_dispatch.apply_callback(|_| ClearHistory);
+
+    let rolls = history.rolls.iter().rev().map(|roll| {
+        let setup = match roll
+            .setup
+            .as_ref()
+            .and_then(SetupKey::index)
+            .map(|index| &SETUPS[index])
+        {
+            Some(setup) => format!(
+                "{} - {} - {}",
+                setup.content.name(settings.game_language),
+                setup.chapter.0,
+                setup.name(settings.game_language)
+            ),
+            None => settings.msg.custom().to_string(),
+        };
+        let mut monsters = roll
+            .monsters
+            .iter()
+            .map(|monster| monster.name(settings.game_language, &homebrew.monsters))
+            .collect::<Vec<_>>();
+        monsters.sort_unstable();
+        html! {
+            <tr>
+                <td>{setup}</td>
+                <td>{monsters.join(", ")}</td>
+            </tr>
+        }
+    });
+
+    html! {
+        <>
+            <div class="form-check">
+              <input
+                type="checkbox"
+                class="form-check-input"
+                value=""
+                id="freshness"
+                checked={settings.freshness}
+                onchange={toggle_freshness}
+              />
+              <label class="form-check-label" for="freshness">
+                {settings.msg.freshness_before()}{" "}
+                <input
+                    type="number"
+                    min="1"
+                    style="width: 4em"
+                    value={settings.freshness_rolls.to_string()}
+                    onchange={change_rolls}
+                />
+                {" "}
+                <select class="form-select form-select-sm d-inline-block w-auto" onchange={change_unit}>
+                    <option value="rolls" selected={!settings.freshness_chapters}>
+                        {settings.msg.freshness_after()}
+                    </option>
+                    <option value="chapters" selected={settings.freshness_chapters}>
+                        {settings.msg.freshness_after_chapters()}
+                    </option>
+                </select>
+              </label>
+            </div>
+            <details>
+                <summary>{settings.msg.previous_rolls()}{format!(" ({})", history.rolls.len())}</summary>
+                if !history.rolls.is_empty() {
+                    <Button style={Color::Danger} outline={true} size={ButtonSize::Small} onclick={clear}>{settings.msg.clear()}</Button>
+                    <table class="table" style="width: auto">
+                        <tbody>
+                            {for rolls}
+                        </tbody>
+                    </table>
+                }
+            </details>
+        </>
+    }
+}
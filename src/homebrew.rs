@@ -0,0 +1,327 @@
+//! Monsters defined by the user, they join the pool of the random monsters.
+
+use crate::Settings;
+use crate::history::History;
+use crate::inventory::{Inventory, SetCopies};
+use crate::select::SelectStore;
+use engine::game::{AnyMonster, Color, Content, GameLanguage, Monster};
+use engine::homebrew::{Homebrew, HomebrewId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::rc::Rc;
+use web_sys::wasm_bindgen::JsCast;
+use web_sys::wasm_bindgen::closure::Closure;
+use web_sys::{FileReader, HtmlInputElement, HtmlSelectElement};
+use yew::{
+    Callback, Event, Html, InputEvent, TargetCast, function_component, html, use_mut_ref, use_state,
+};
+use yew_bootstrap::component::{Alert, Button, ButtonSize};
+use yew_bootstrap::icons::BI;
+use yewdux::{Dispatch, Reducer, Store, use_store};
+
+/// The colors a homebrew monster can have.
+const COLORS: [Color; 4] = [Color::White, Color::Gray, Color::Black, Color::Commander];
+
+/// The maximum size of an image in KB, it's stored in the local storage of the browser.
+const MAX_IMAGE_KB: u32 = 200;
+
+/// The homebrew monsters and their images.
+#[derive(Clone, Default, PartialEq, Deserialize, Serialize, Store)]
+#[store(storage = "local", storage_tab_sync)]
+#[serde(default)]
+pub(crate) struct HomebrewStore {
+    pub(crate) monsters: Vec<Homebrew>,
+    /// the uploaded images as data URLs
+    images: Vec<(HomebrewId, String)>,
+    /// the id of the next monster, ids of removed monsters aren't reused
+    next_id: u32,
+}
+
+impl HomebrewStore {
+    /// Whether the monster can be drawn: its content is enabled resp. it's not removed.
+    pub(crate) fn is_available(&self, monster: AnyMonster, content: &HashSet<Content>) -> bool {
+        match monster {
+            AnyMonster::Game(monster) => content.contains(&monster.content()),
+            AnyMonster::Homebrew(_) => monster.homebrew(&self.monsters).is_some(),
+        }
+    }
+
+    /// The image of the monster, the uploaded one or the one of the miniature.
+    pub(crate) fn image(&self, monster: AnyMonster) -> Option<String> {
+        let uploaded = self
+            .images
+            .iter()
+            .find(|(id, _)| AnyMonster::Homebrew(*id) == monster)
+            .map(|(_, image)| image.clone());
+        uploaded.or_else(|| match monster.sculpt(&self.monsters) {
+            AnyMonster::Game(monster) => monster.image().map(|image| format!("miniature/{image}")),
+            AnyMonster::Homebrew(_) => None,
+        })
+    }
+}
+
+/// The name of the content of the monster.
+pub(crate) fn content_name(monster: AnyMonster, settings: &Settings) -> &'static str {
+    match monster {
+        AnyMonster::Game(monster) => monster.content().name(settings.game_language),
+        AnyMonster::Homebrew(_) => settings.msg.homebrew(),
+    }
+}
+
+/// Adds the monster with the next id.
+struct AddHomebrew(Homebrew, Option<String>);
+impl Reducer<HomebrewStore> for AddHomebrew {
+    fn apply(self, mut rc_store: Rc<HomebrewStore>) -> Rc<HomebrewStore> {
+        let store = Rc::make_mut(&mut rc_store);
+        let mut homebrew = self.0;
+        // the stores of earlier versions have no `next_id`
+        let id = store
+            .monsters
+            .iter()
+            .map(|monster| monster.id.0 + 1)
+            .max()
+            .unwrap_or_default()
+            .max(store.next_id);
+        homebrew.id = HomebrewId(id);
+        store.next_id = id + 1;
+        if let Some(image) = self.1 {
+            store.images.push((homebrew.id, image));
+        }
+        store.monsters.push(homebrew);
+        rc_store
+    }
+}
+
+/// Removes the monster, from the other stores as well.
+struct RemoveHomebrew(HomebrewId);
+impl Reducer<HomebrewStore> for RemoveHomebrew {
+    fn apply(self, mut rc_store: Rc<HomebrewStore>) -> Rc<HomebrewStore> {
+        let store = Rc::make_mut(&mut rc_store);
+        store.monsters.retain(|monster| monster.id != self.0);
+        store.images.retain(|(id, _)| *id != self.0);
+
+        let monster = AnyMonster::Homebrew(self.0);
+        Dispatch::<Settings>::global().reduce_mut(|settings| {
+            settings.banned.remove(&monster);
+            settings.favorites.remove(&monster);
+        });
+        Dispatch::<Inventory>::global().apply(SetCopies(monster, None));
+        Dispatch::<History>::global().reduce_mut(|history| history.forget(monster));
+        rc_store
+    }
+}
+
+#[function_component]
+pub(crate) fn HomebrewEditor() -> Html {
+    let (settings, _) = use_store::<Settings>();
+    let (store, dispatch) = use_store::<HomebrewStore>();
+    let names = use_state(|| vec![String::new(); GameLanguage::iter().count()]);
+    let color = use_state(|| Color::White);
+    let miniature = use_state(|| None::<Monster>);
+    let image = use_state(|| None::<String>);
+    let too_large = use_state(|| false);
+    // the reader of the image and its callback, which are dropped with the next image
+    let reading = use_mut_ref(|| None::<(FileReader, Closure<dyn FnMut()>)>);
+
+    let list = store.monsters.iter().map(|monster| {
+        let id = monster.id;
+        let remove = {
+            let dispatch = dispatch.clone();
+            Callback::from(move |_| {
+                dispatch.apply(RemoveHomebrew(id));
+                // the removed monster may be in the current roll
+                Dispatch::<SelectStore>::global().reduce_mut(|select| {
+                    select.adjust_content(&Dispatch::<Settings>::global().get());
+                });
+            })
+        };
+        html! {
+            <tr>
+                <td>
+                    {monster.color.short(settings.game_language)}{" - "}
+                    {monster.name(settings.game_language)}
+                    if let Some(miniature) = monster.miniature {
+                        {" - "}{miniature.name(settings.game_language)}
+                    }
+                </td>
+                <td>
+                    if let Some(image) = store.image(AnyMonster::Homebrew(id)) {
+                        <img src={image} style="max-width: 50px; max-height: 50px;" />
+                    }
+                </td>
+                <td>
+                    <Button style={yew_bootstrap::util::Color::Danger} outline={true} size={ButtonSize::Small} onclick={remove}>{BI::TRASH}</Button>
+                </td>
+            </tr>
+        }
+    });
+
+    let name_inputs = GameLanguage::iter().enumerate().map(|(index, language)| {
+        let oninput = {
+            let names = names.clone();
+            Callback::from(move |e: InputEvent| {
+                let input = e.target_unchecked_into::<HtmlInputElement>();
+                let mut new_names = (*names).clone();
+                new_names[index] = input.value();
+                names.set(new_names);
+            })
+        };
+        html! {
+            <input
+                type="text"
+                class="form-control mb-1"
+                placeholder={language.as_str()}
+                value={names[index].clone()}
+                oninput={oninput}
+            />
+        }
+    });
+
+    let change_color = {
+        let color = color.clone();
+        Callback::from(move |e: Event| {
+            let select = e.target_unchecked_into::<HtmlSelectElement>();
+            if let Some(c) = select
+                .value()
+                .parse()
+                .ok()
+                .and_then(|i: usize| COLORS.get(i))
+            {
+                color.set(*c);
+            }
+        })
+    };
+    let colors = COLORS.iter().enumerate().map(|(index, c)| {
+        html! {
+            <option value={index.to_string()} selected={*color == *c}>{c.name(settings.game_language)}</option>
+        }
+    });
+
+    let change_miniature = {
+        let miniature = miniature.clone();
+        Callback::from(move |e: Event| {
+            let select = e.target_unchecked_into::<HtmlSelectElement>();
+            miniature.set(
+                select
+                    .value()
+                    .parse()
+                    .ok()
+                    .and_then(|value: u8| Monster::try_from(value)),
+            );
+        })
+    };
+    let mut miniatures = Monster::iter()
+        .filter(|monster| monster.miniature().is_none())
+        .map(|monster| (monster, monster.name(settings.game_language)))
+        .collect::<Vec<_>>();
+    miniatures.sort_by_key(|(_, name)| *name);
+    let miniatures = miniatures.into_iter().map(|(monster, name)| {
+        html! {
+            <option value={monster.into().to_string()} selected={*miniature == Some(monster)}>{name}</option>
+        }
+    });
+
+    let change_image = {
+        let (image, too_large, reading) = (image.clone(), too_large.clone(), reading.clone());
+        Callback::from(move |e: Event| {
+            if let Some((reader, _)) = reading.borrow_mut().take() {
+                reader.set_onload(None);
+                reader.abort();
+            }
+            let input = e.target_unchecked_into::<HtmlInputElement>();
+            let file = input.files().and_then(|files| files.get(0));
+            too_large.set(false);
+            let Some(file) = file else {
+                image.set(None);
+                return;
+            };
+            if file.size() > f64::from(MAX_IMAGE_KB * 1000) {
+                too_large.set(true);
+                image.set(None);
+                input.set_value("");
+                return;
+            }
+            let Ok(reader) = FileReader::new() else {
+                return;
+            };
+            let onload = {
+                let (image, reader) = (image.clone(), reader.clone());
+                Closure::<dyn FnMut()>::new(move || {
+                    image.set(reader.result().ok().and_then(|result| result.as_string()));
+                })
+            };
+            reader.set_onload(Some(onload.as_ref().unchecked_ref()));
+            let _ = reader.read_as_data_url(&file);
+            *reading.borrow_mut() = Some((reader, onload));
+        })
+    };
+
+    let add = {
+        let (names, color, miniature, image) = (
+            names.clone(),
+            color.clone(),
+            miniature.clone(),
+            image.clone(),
+        );
+        let dispatch = dispatch.clone();
+        Callback::from(move |_| {
+            let homebrew = Homebrew {
+                // is set by `AddHomebrew`
+                id: HomebrewId(0),
+                names: GameLanguage::iter()
+                    .zip(names.iter())
+                    .filter(|(_, name)| !name.trim().is_empty())
+                    .map(|(language, name)| (language, name.trim().to_string()))
+                    .collect(),
+                color: *color,
+                miniature: *miniature,
+            };
+            dispatch.apply(AddHomebrew(homebrew, (*image).clone()));
+            names.set(vec![String::new(); names.len()]);
+            image.set(None);
+        })
+    };
+
+    html! {
+        <details>
+            <summary>{settings.msg.homebrew_monsters()}{format!(" ({})", store.monsters.len())}</summary>
+            <div class="form-text">{settings.msg.homebrew_help()}</div>
+            if !store.monsters.is_empty() {
+                <table class="table" style="width: auto">
+                    <tbody>
+                        {for list}
+                    </tbody>
+                </table>
+            }
+            <div style="max-width: 30em">
+                {for name_inputs}
+                <select class="form-select mb-1" onchange={change_color}>
+                    {for colors}
+                </select>
+                <select class="form-select mb-1" onchange={change_miniature}>
+                    <option value="" selected={miniature.is_none()}>{settings.msg.own_miniature()}</option>
+                    {for miniatures}
+                </select>
+                <label class="form-label">
+                    {settings.msg.image()}
+                    <input type="file" class="form-control" accept="image/*" onchange={change_image} />
+                </label>
+                if *too_large {
+                    <Alert style={yew_bootstrap::util::Color::Warning}>
+                        {settings.msg.image_too_large(&MAX_IMAGE_KB.to_string())}
+                    </Alert>
+                }
+                <div>
+                    <Button
+                        style={yew_bootstrap::util::Color::Primary}
+                        size={ButtonSize::Small}
+                        disabled={names[0].trim().is_empty()}
+                        onclick={add}
+                    >
+                        {settings.msg.add()}
+                    </Button>
+                </div>
+            </div>
+        </details>
+    }
+}
@@ -0,0 +1,193 @@
+//! The miniatures: the owned ones, rolls avoid sculpts of which not enough figures are owned, and the
+//! ones a roll needs.
+
+use crate::Settings;
+use crate::homebrew::{HomebrewStore, content_name};
+use engine::game::AnyMonster;
+use engine::roll::{Item, RollSettings, figures, shortages};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::rc::Rc;
+use web_sys::HtmlInputElement;
+use yew::{Callback, Event, Html, TargetCast, function_component, html};
+use yew_bootstrap::component::Alert;
+use yewdux::{Reducer, Store, use_store};
+
+/// The owned copies of the miniatures.
+#[derive(Clone, Default, PartialEq, Deserialize, Serialize, Store)]
+#[store(storage = "local", storage_tab_sync)]
+#[serde(default)]
+pub(crate) struct Inventory {
+    /// the copies of each sculpt, sculpts which are not listed are unlimited
+    #[serde(deserialize_with = "engine::game::deserialize_monster_counts")]
+    copies: Vec<(AnyMonster, usize)>,
+}
+
+impl Inventory {
+    pub(crate) fn copies(&self) -> HashMap<AnyMonster, usize> {
+        self.copies.iter().copied().collect()
+    }
+}
+
+/// Sets the copies of a sculpt, `None` for unlimited.
+pub(crate) struct SetCopies(pub(crate) AnyMonster, pub(crate) Option<usize>);
+impl Reducer<Inventory> for SetCopies {
+    fn apply(self, mut rc_inventory: Rc<Inventory>) -> Rc<Inventory> {
+        let inventory = Rc::make_mut(&mut rc_inventory);
+        inventory.copies.retain(|(sculpt, _)| *sculpt != self.0);
+        if let Some(copies) = self.1 {
+            inventory.copies.push((self.0, copies));
+        }
+        rc_inventory
+    }
+}
+
+/// The settings to count the figures of a roll.
+fn roll_settings(
+    settings: &Settings,
+    homebrew: &HomebrewStore,
+    inventory: &Inventory,
+) -> RollSettings {
+    RollSettings {
+        copies: inventory.copies(),
+        players: Some(settings.players),
+        homebrew: homebrew.monsters.clone(),
+        ..RollSettings::default()
+    }
+}
+
+/// Warns about the sculpts of the roll of which not enough figures are owned.
+pub(crate) fn render_shortages(
+    settings: &Settings,
+    homebrew: &HomebrewStore,
+    inventory: &Inventory,
+    output: &[Item],
+) -> Html {
+    shortages(&roll_settings(settings, homebrew, inventory), output)
+        .into_iter()
+        .map(|shortage| {
+            html! {
+                <Alert style={yew_bootstrap::util::Color::Warning}>
+                    {settings.msg.shortage(
+                        shortage.sculpt.name(settings.game_language, &homebrew.monsters),
+                        &shortage.needed.to_string(),
+                        &shortage.owned.to_string(),
+                    )}
+                </Alert>
+            }
+        })
+        .collect()
+}
+
+/// The miniatures to take out of the boxes for the roll, grouped by sculpt.
+pub(crate) fn render_pull_list(
+    settings: &Settings,
+    homebrew: &HomebrewStore,
+    inventory: &Inventory,
+    output: &[Item],
+) -> Html {
+    let figures = figures(&roll_settings(settings, homebrew, inventory), output);
+    if figures.is_empty() {
+        return html! {};
+    }
+    let rows = figures.into_iter().map(|(sculpt, count)| {
+        html! {
+            <tr>
+                <td>
+                    if let Some(image) = homebrew.image(sculpt) {
+                        <img src={image} style="max-width: 50px; max-height: 50px;" />
+                    }
+                </td>
+                <td>{format!("{count}\u{d7}")}</td>
+                <td>{sculpt.name(settings.game_language, &homebrew.monsters)}</td>
+                <td>{content_name(sculpt, settings)}</td>
+            </tr>
+        }
+    });
+    html! {
+        <>
+            <h6>{settings.msg.pull_list()}</h6>
+            <table class="table align-middle" style="width: auto">
+                <tbody>
+                    {for rows}
+                </tbody>
+            </table>
+        </>
+    }
+}
+
+#[function_component]
+pub(crate) fn InventoryEditor() -> Html {
+    let (settings, _) = use_store::<Settings>();
+    let (homebrew, _) = use_store::<HomebrewStore>();
+    let (inventory, dispatch) = use_store::<Inventory>();
+
+    let monsters = AnyMonster::iter(&homebrew.monsters)
+        .filter(|monster| homebrew.is_available(*monster, &settings.content))
+        .collect::<Vec<_>>();
+    let mut sculpts = monsters
+        .iter()
+        .copied()
+        .filter(|monster| monster.sculpt(&homebrew.monsters) == *monster)
+        .map(|sculpt| {
+            let mut others = monsters
+                .iter()
+                .filter(|monster| **monster != sculpt)
+                .filter(|monster| monster.sculpt(&homebrew.monsters) == sculpt)
+                .map(|monster| monster.name(settings.game_language, &homebrew.monsters))
+                .collect::<Vec<_>>();
+            others.sort_unstable();
+            let name = sculpt.name(settings.game_language, &homebrew.monsters);
+            (sculpt, name, others.join(", "))
+        })
+        .collect::<Vec<_>>();
+    sculpts.sort_by_key(|(_, name, _)| *name);
+
+    let copies = inventory.copies();
+    let sculpts = sculpts.into_iter().map(|(sculpt, name, others)| {
+        let onchange = {
+            let dispatch = dispatch.clone();
+            Callback::from(move |e: Event| {
+                let input = e.target_unchecked_into::<HtmlInputElement>();
+                let value = input.value();
+                let value = value.trim();
+                if value.is_empty() {
+                    dispatch.apply(SetCopies(sculpt, None));
+                } else if let Ok(copies) = value.parse() {
+                    dispatch.apply(SetCopies(sculpt, Some(copies)));
+                }
+            })
+        };
+        html! {
+            <tr>
+                <td>
+                    {name}
+                    if !others.is_empty() {
+                        <div class="form-text">{others}</div>
+                    }
+                </td>
+                <td>
+                    <input
+                        type="number"
+                        min="0"
+                        style="width: 5em"
+                        value={copies.get(&sculpt).map(ToString::to_string).unwrap_or_default()}
+                        onchange={onchange}
+                    />
+                </td>
+            </tr>
+        }
+    });
+
+    html! {
+        <details>
+            <summary>{settings.msg.inventory()}{format!(" ({})", copies.len())}</summary>
+            <div class="form-text">{settings.msg.inventory_help()}</div>
+            <table class="table" style="width: auto">
+                <tbody>
+                    {for sculpts}
+                </tbody>
+            </table>
+        </details>
+    }
+}
@@ -1,34 +1,52 @@
 #![forbid(unsafe_code)]
-#![deny(unused_crate_dependencies)]
+// the dependencies of the roll binary (feature cli) are not used by the app
+#![cfg_attr(not(feature = "cli"), deny(unused_crate_dependencies))]
 #![warn(clippy::std_instead_of_core)]
 #![warn(clippy::pedantic)]
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::module_name_repetitions)]
 #![allow(clippy::similar_names)]
+#![allow(clippy::struct_excessive_bools)]
 #![allow(clippy::too_many_lines)]
 #![allow(clippy::unsafe_derive_deserialize)]
 
-use crate::game::{Chapter, Content, GameLanguage, Number};
+use crate::custom::CustomImport;
+use crate::generate::AdventureGenerator;
+use crate::history::Freshness;
+use crate::homebrew::{HomebrewEditor, HomebrewStore};
+use crate::inventory::{Inventory, InventoryEditor};
 use crate::msg::MsgLanguage;
-use crate::select::{Item, Randomize, Select, SelectStore};
+use crate::play::ChapterPlay;
+use crate::preferences::MonsterPreferences;
+use crate::select::{Randomize, Reroll, RollAgain, Seed, Select, SelectStore, ToggleLock};
 use core::ops::Deref;
+use engine::game::{AnyMonster, Chapter, Content, GameLanguage, Number};
+use engine::roll::Item;
 use getrandom as _; // is only used indirectly through rand but is required to activate feature
 use std::collections::{HashSet, VecDeque};
 use std::rc::Rc;
-use yew::{Html, function_component, html};
+use web_sys::HtmlInputElement;
+use yew::{Callback, Event, Html, TargetCast, function_component, html};
 use yew_bootstrap::component::form::{FormControl, FormControlType};
-use yew_bootstrap::component::{Alert, Button};
+use yew_bootstrap::component::{Alert, Button, ButtonSize};
 use yew_bootstrap::icons::BI;
 use yew_bootstrap::util::Color;
 use yewdux::{Dispatch, Reducer, Store, use_store};
 
-pub(crate) mod game;
+mod custom;
+mod generate;
+mod history;
+mod homebrew;
+mod inventory;
 pub(crate) mod msg;
+mod play;
+mod preferences;
 pub(crate) mod select;
-mod setup;
+mod share;
 
 #[derive(Clone, PartialEq, serde::Serialize, serde::Deserialize, Store)]
 #[store(storage = "local", storage_tab_sync)]
+#[serde(default)]
 pub(crate) struct Settings {
     pub(crate) game_language: GameLanguage,
     pub(crate) msg: MsgLanguage,
@@ -36,8 +54,19 @@ pub(crate) struct Settings {
     pub(crate) preset: bool,
     pub(crate) players: Number,
     pub(crate) preset_content: Content,
+    /// the imported setups are shown instead of `preset_content`
+    pub(crate) preset_custom: bool,
     pub(crate) preset_chapter: Chapter,
     pub(crate) new_list_mode: bool,
+    #[serde(deserialize_with = "engine::game::deserialize_monster_set")]
+    pub(crate) banned: HashSet<AnyMonster>,
+    #[serde(deserialize_with = "engine::game::deserialize_monster_set")]
+    pub(crate) favorites: HashSet<AnyMonster>,
+    pub(crate) freshness: bool,
+    pub(crate) freshness_rolls: usize,
+    /// `freshness_rolls` counts chapters instead of rolls
+    pub(crate) freshness_chapters: bool,
+    pub(crate) sticky: bool,
 }
 
 impl Default for Settings {
@@ -49,8 +78,15 @@ impl Default for Settings {
             preset: true,
             players: Number::Five,
             preset_content: Content::Core,
+            preset_custom: false,
             preset_chapter: Chapter(1),
             new_list_mode: true,
+            banned: HashSet::new(),
+            favorites: HashSet::new(),
+            freshness: false,
+            freshness_rolls: 3,
+            freshness_chapters: false,
+            sticky: false,
         }
     }
 }
@@ -121,6 +157,8 @@ impl Reducer<Settings> for ToggleListType {
 fn App() -> Html {
     let (settings, dispatch) = use_store::<Settings>();
     let (select, select_dispatch) = use_store::<SelectStore>();
+    let (homebrew, _) = use_store::<HomebrewStore>();
+    let (inventory, _) = use_store::<Inventory>();
 
     let game_languages = GameLanguage::iter()
         .zip(GameLanguage::names())
@@ -165,8 +203,10 @@ fn App() -> Html {
     let mut contents = Content::iter()
         .map(|content| (content, content.name(settings.game_language)))
         .collect::<Vec<_>>();
-    contents.sort_by_key(|(c, _n)| c.order_name(settings.game_language));
-    let has_unknown = contents.iter().any(|(_, n)| n.ends_with('*'));
+    contents.sort_by_key(|(c, n)| (c.order(), *n));
+    let has_unknown = contents
+        .iter()
+        .any(|(c, _)| !c.is_translated(settings.game_language));
     let contents = contents.into_iter().map(|(content, name)| {
         let id = format!("content:{}", content.into());
         let onchange = dispatch.apply_callback(move |_| content);
@@ -181,23 +221,42 @@ fn App() -> Html {
                 onchange={onchange}
               />
               <label class="form-check-label" for={id}>
-                {name}
+                {name}{if content.is_translated(settings.game_language) {""} else {"*"}}
               </label>
             </div>
         }
     });
 
     let list = if settings.new_list_mode {
-        render_list_new(&settings, select.output.borrow())
+        render_list_new(
+            &settings,
+            &homebrew,
+            &select_dispatch,
+            &select.locked,
+            select.output.borrow(),
+        )
     } else {
         select
             .output
             .borrow()
             .iter()
-            .map(|item| render_list_old(&settings, item))
+            .map(|item| {
+                render_list_old(&settings, &homebrew, &select_dispatch, &select.locked, item)
+            })
             .collect::<Vec<_>>()
     };
     let randomize = select_dispatch.apply_callback(|_| Randomize);
+    let roll_again = select_dispatch.apply_callback(|_| RollAgain);
+    let share_link = share::link(&settings, &select);
+    let change_seed = {
+        let select_dispatch = select_dispatch.clone();
+        Callback::from(move |e: Event| {
+            let input = e.target_unchecked_into::<HtmlInputElement>();
+            if let Ok(seed) = input.value().trim().parse() {
+                select_dispatch.apply(Seed(seed));
+            }
+        })
+    };
     let toggle_list_type = dispatch.apply_callback(|_| ToggleListType);
 
     let click_use_preset = dispatch.apply_callback(|_| true);
@@ -227,17 +286,17 @@ fn App() -> Html {
             <nav class="navbar sticky-top bg-body-tertiary">
               <div class="container-fluid">
                 <a class="navbar-brand" href="#">
-                  {"Unofficial Drunagor Randomizer"}
+                  {settings.msg.title()}
                 </a>
 
                 <div class="navbar-nav">
                   <ul class="nav nav-pills">
                     <div>
-                        {"Game language: "}
+                        {settings.msg.game_language()}{": "}
                         <div class="btn-group" role="group">
                             {for game_languages}
                         </div>
-                        {" UI language: "}
+                        {" "}{settings.msg.ui_language()}{": "}
                         <div class="btn-group" role="group">
                             {for msg_languages}
                         </div>
@@ -253,7 +312,7 @@ fn App() -> Html {
                     <div class="accordion-item">
                       <h2 class="accordion-header">
                         <button class="accordion-button" type="button" data-bs-toggle="collapse" data-bs-target="#collapseOne" aria-expanded="true" aria-controls="collapseOne">
-                          {"Settings"}
+                          {settings.msg.settings()}
                         </button>
                       </h2>
                       <div id="collapseOne" class={"accordion-collapse collapse show"} data-bs-parent="#accordionExample">
@@ -261,11 +320,11 @@ fn App() -> Html {
                           {for contents}
                           if has_unknown {
                             <div>
-                              {"* = not translated"}
+                              {settings.msg.not_translated()}
                             </div>
                           }
         <hr/>
-        {"Number of players:"}<br/>
+        {settings.msg.number_of_players()}<br/>
         {for players}
         if settings.content.contains(&Content::Core) {
             <hr/>
@@ -275,7 +334,7 @@ fn App() -> Html {
                 name={"use_preset"}
                 checked={settings.preset}
                 onclick={click_use_preset}
-                label={"Use preset monster setups"}
+                label={settings.msg.use_preset()}
             />
             <FormControl
                 id={"click_dont_use_preset"}
@@ -283,9 +342,16 @@ fn App() -> Html {
                 name={"dont_use_preset"}
                 checked={!settings.preset}
                 onclick={click_dont_use_preset}
-                label={"Use custom monster setups"}
+                label={settings.msg.use_custom()}
             />
         }
+        <hr/>
+        <MonsterPreferences/>
+        <Freshness/>
+        <CustomImport/>
+        <AdventureGenerator/>
+        <HomebrewEditor/>
+        <InventoryEditor/>
                         </div>
                       </div>
                     </div>
@@ -293,7 +359,7 @@ fn App() -> Html {
                     <div class="accordion-item">
                       <h2 class="accordion-header">
                         <button class="accordion-button collapsed" type="button" data-bs-toggle="collapse" data-bs-target="#collapseTwo" aria-expanded="false" aria-controls="collapseTwo">
-                          {"Selection"}
+                          {settings.msg.selection()}
                         </button>
                       </h2>
                       <div id="collapseTwo" class="accordion-collapse collapse" data-bs-parent="#accordionExample">
@@ -312,27 +378,45 @@ fn App() -> Html {
                     <div class="accordion-item">
                       <h2 class="accordion-header">
                         <button class="accordion-button collapsed" type="button" data-bs-toggle="collapse" data-bs-target="#collapseThree" aria-expanded="false" aria-controls="collapseThree">
-                          {"Monster Setup"}
+                          {settings.msg.monster_setup()}
                         </button>
                       </h2>
                       <div id="collapseThree" class={"accordion-collapse collapse"} data-bs-parent="#accordionExample">
                         <div class="accordion-body">
-                            if let Some((content, chapter, name)) = &select.setup {
+                            if let Some(setup) = select.setup.filter(|setup| setup.exists()) {
                                 <Alert style={Color::Light}>
-                                    {content.name(settings.game_language)}{" - "}
-                                    {chapter.0}{" - "}
-                                    {name}{" "}
-                                    <Button style={Color::Primary} outline={true} onclick={randomize}>{BI::ARROW_COUNTERCLOCKWISE}</Button>
+                                    {setup.title(&settings)}
                                 </Alert>
+                                <ChapterPlay/>
                             }
+                            <div class="input-group mb-3" style="width: auto">
+                                <span class="input-group-text">{settings.msg.seed()}</span>
+                                <input
+                                    type="number"
+                                    class="form-control"
+                                    min="0"
+                                    id="seed"
+                                    value={select.seed.to_string()}
+                                    onchange={change_seed}
+                                />
+                                <button type="button" class="btn btn-outline-primary" title={settings.msg.roll_again()} onclick={roll_again}>{BI::ARROW_REPEAT}</button>
+                                <button type="button" class="btn btn-outline-primary" title={settings.msg.randomize()} onclick={randomize}>{BI::ARROW_COUNTERCLOCKWISE}</button>
+                                <a class="btn btn-outline-primary" href={share_link} title={settings.msg.share_link()}>{BI::SHARE}</a>
+                            </div>
+                            if select.output.borrow().iter().any(|item| matches!(item.monster, Some(AnyMonster::Homebrew(_)))) {
+                                <p class="form-text">{settings.msg.share_homebrew()}</p>
+                            }
+                            {select::render_missing(&settings, &select)}
+                            {inventory::render_shortages(&settings, &homebrew, &inventory, &select.output.borrow())}
                             if select.output.borrow().is_empty() {
-                                <Alert style={Color::Secondary}>{"This setup has no monsters"}</Alert>
+                                <Alert style={Color::Secondary}>{settings.msg.no_monsters()}</Alert>
                             }else{
                                 <table class="table" style="width: auto">
                                     <tbody>
                                         {for list}
                                     </tbody>
                                 </table>
+                                {inventory::render_pull_list(&settings, &homebrew, &inventory, &select.output.borrow())}
                             }
                             <div>
                             <div class="form-check">
@@ -345,14 +429,14 @@ fn App() -> Html {
                                 onchange={toggle_list_type}
                               />
                               <label class="form-check-label" for="toggle-list-type">
-                                {"New list type"}
+                                {settings.msg.new_list_type()}
                               </label>
                             </div>
 
                             </div>
             if select.output.borrow().iter().any(|item| item.preset) {
                             <div>
-                {"* = Preset monsters"}
+                {settings.msg.preset_monsters()}
                             </div>
             }
                         </div>
@@ -362,7 +446,7 @@ fn App() -> Html {
                     <div class="accordion-item" style="display: none">
                       <h2 class="accordion-header">
                         <button class="accordion-button collapsed" type="button" data-bs-toggle="collapse" data-bs-target="#collapseThree" aria-expanded="false" aria-controls="collapseThree">
-                          {"Monster Setup"}
+                          {settings.msg.monster_setup()}
                         </button>
                       </h2>
                       <div id="collapseThree" class={"accordion-collapse collapse"} data-bs-parent="#accordionExample">
@@ -378,26 +462,26 @@ fn App() -> Html {
               <nav class="navbar sticky-bottom bg-body-tertiary">
                 <div class="container-fluid">
                   <h5 class="mb-0">
-                    {"Written by Alex."}
+                    {settings.msg.written_by()}
                   </h5>
 
                   <div class="ms-auto">
-                    <a target="_blank" href="https://alexkazik.github.io/boardgames.html">{"My other board game tools"}</a>
+                    <a target="_blank" href="https://alexkazik.github.io/boardgames.html">{settings.msg.other_tools()}</a>
                   </div>
 
                   <div class="ms-auto">
-                    {"Favicon by "}<a target="_blank" href="https://boardgameheaven.eu">{"Board Game Heaven"}</a>
+                    {settings.msg.favicon_by()}{" "}<a target="_blank" href="https://boardgameheaven.eu">{"Board Game Heaven"}</a>
                   </div>
 
                   <div class="ms-auto">
-                    {"Version: "}{env!("CARGO_PKG_VERSION")}
+                    {settings.msg.version()}{": "}{env!("CARGO_PKG_VERSION")}
                     <a
                       href="https://github.com/alexkazik/spawning-drunagor"
                       target="_blank"
                       class="btn btn-dark btn-sm ms-4"
                     >
                       <i class="bi bi-github"></i>
-                      {"Source"}
+                      {settings.msg.source()}
                     </a>
                   </div>
                 </div>
@@ -406,21 +490,54 @@ fn App() -> Html {
       }
 }
 
-fn render_list_old(settings: &Rc<Settings>, item: &Item) -> Html {
+fn render_random_actions(
+    dispatch: &Dispatch<SelectStore>,
+    locked: &[(engine::game::Color, engine::game::Level)],
+    item: &Item,
+) -> Html {
+    if item.preset {
+        html! {}
+    } else {
+        let (color, level) = (item.color, item.level);
+        let is_locked = locked.contains(&(color, level));
+        let reroll = dispatch.apply_callback(move |_| Reroll(color, level));
+        let toggle_lock = dispatch.apply_callback(move |_| ToggleLock(color, level));
+        html! {
+            <>
+                <Button style={Color::Secondary} outline={true} size={ButtonSize::Small} onclick={reroll}>{BI::ARROW_REPEAT}</Button>
+                {" "}
+                <Button style={Color::Secondary} outline={!is_locked} size={ButtonSize::Small} onclick={toggle_lock}>
+                    {if is_locked {BI::LOCK_FILL} else {BI::UNLOCK}}
+                </Button>
+            </>
+        }
+    }
+}
+
+fn render_list_old(
+    settings: &Rc<Settings>,
+    homebrew: &HomebrewStore,
+    dispatch: &Dispatch<SelectStore>,
+    locked: &[(engine::game::Color, engine::game::Level)],
+    item: &Item,
+) -> Html {
     if let Some(m) = item.monster {
+        let miniature = m.sculpt(&homebrew.monsters);
         let mut fade = "";
         if item.number > settings.players {
             fade = "opacity: 0.5";
         }
+        let reroll = render_random_actions(dispatch, locked, item);
         if item.color.is_any_commander() {
             html! {
                 <tr>
                     <td style={fade}>
                         {BI::PERSON_WALKING}{item.number.as_str()}{" "}
                         {item.color.short(settings.game_language)}{" - "}
-                        {m.name(settings.game_language)}
+                        {m.name(settings.game_language, &homebrew.monsters)}
                         {if item.preset {"*"}else{""}}
                     </td>
+                    <td>{reroll}</td>
                 </tr>
             }
         } else {
@@ -431,23 +548,44 @@ fn render_list_old(settings: &Rc<Settings>, item: &Item) -> Html {
                         if !item.color.is_any_special() {
                             {item.color.short(settings.game_language)}{" - "}
                         }
-                        {m.name(settings.game_language)}{if item.preset {"*"}else{""}}
+                        {m.name(settings.game_language, &homebrew.monsters)}{if item.preset {"*"}else{""}}
                         if !item.color.is_any_commander() && !item.color.is_any_special() {
                             {" - "}{item.level.name(settings.game_language)}
                         }
-                        if let Some(monster) = m.miniature() {
-                            {" - "}{monster.name(settings.game_language)}
+                        if miniature != m {
+                            {" - "}{miniature.name(settings.game_language, &homebrew.monsters)}
                         }
                     </td>
+                    <td>{reroll}</td>
                 </tr>
             }
         }
+    } else if item.missing.is_some() {
+        html! {
+            <tr>
+                <td>
+                    {BI::PERSON_WALKING}{item.number.as_str()}{" "}
+                    {item.color.short(settings.game_language)}{" - "}
+                    {BI::EXCLAMATION_TRIANGLE}
+                    if !item.color.is_any_commander() {
+                        {" - "}{item.level.name(settings.game_language)}
+                    }
+                </td>
+                <td></td>
+            </tr>
+        }
     } else {
         html! {}
     }
 }
 
-fn render_list_new(settings: &Rc<Settings>, output: impl Deref<Target = Vec<Item>>) -> Vec<Html> {
+fn render_list_new(
+    settings: &Rc<Settings>,
+    homebrew: &HomebrewStore,
+    dispatch: &Dispatch<SelectStore>,
+    locked: &[(engine::game::Color, engine::game::Level)],
+    output: impl Deref<Target = Vec<Item>>,
+) -> Vec<Html> {
     let mut result = Vec::new();
 
     let mut todo = output
@@ -499,16 +637,18 @@ fn render_list_new(settings: &Rc<Settings>, output: impl Deref<Target = Vec<Item
             })
             .collect::<Vec<_>>();
 
-        let miniature = monster.miniature().unwrap_or(monster);
+        let miniature = monster.sculpt(&homebrew.monsters);
 
-        let size = if let Some(size) = miniature.color().size(settings.game_language) {
+        let size = if let Some(size) = miniature
+            .color(&homebrew.monsters)
+            .and_then(|color| color.size(settings.game_language))
+        {
             format!(", {size}")
         } else {
             String::new()
         };
-        let image = match miniature.image() {
-            Some(image) => {
-                let src = format!("miniature/{image}");
+        let image = match homebrew.image(monster) {
+            Some(src) => {
                 html! {<img src={src} style="max-width: 100px; max-height: 100px;" />}
             }
             None => html! {},
@@ -516,14 +656,15 @@ fn render_list_new(settings: &Rc<Settings>, output: impl Deref<Target = Vec<Item
         result.push(html! {
             <tr>
                 <td>
-                    {monster.name(settings.game_language)}{if item.preset {"*"}else{""}}
+                    {monster.name(settings.game_language, &homebrew.monsters)}{if item.preset {"*"}else{""}}
                     if !item.color.is_any_commander() && !item.color.is_any_special() {
                         {" - "}{item.level.name(settings.game_language)}
                     }
-                    if let Some(monster) = monster.miniature() {
-                        {" - "}{monster.name(settings.game_language)}
+                    if miniature != monster {
+                        {" - "}{miniature.name(settings.game_language, &homebrew.monsters)}
                     }
-                    {" ("}{monster.content().name(settings.game_language)}{size}{")"}
+                    {" ("}{homebrew::content_name(monster, settings)}{size}{")"}
+                    {" "}{render_random_actions(dispatch, locked, item)}
                     <br/>
                     {icons}
                 </td>
@@ -532,6 +673,40 @@ fn render_list_new(settings: &Rc<Settings>, output: impl Deref<Target = Vec<Item
         });
     }
 
+    // slots for which no monster could be drawn
+    let mut missing = Vec::<(&Item, Vec<Html>)>::new();
+    for item in output
+        .iter()
+        .filter(|item| item.missing.is_some())
+        .filter(|item| item.number <= settings.players)
+    {
+        let icon = html! {
+            <div class={format!("box_{}_{}", item.color.css_prefix(), item.level.id())}>{item.color.prefix(settings.game_language)}{item.number.as_str()}</div>
+        };
+        match missing
+            .iter_mut()
+            .find(|(other, _)| other.color == item.color && other.level == item.level)
+        {
+            Some((_, icons)) => icons.push(icon),
+            None => missing.push((item, vec![icon])),
+        }
+    }
+    for (item, icons) in missing {
+        result.push(html! {
+            <tr>
+                <td>
+                    {BI::EXCLAMATION_TRIANGLE}{" "}{item.color.name(settings.game_language)}
+                    if !item.color.is_any_commander() {
+                        {" - "}{item.level.name(settings.game_language)}
+                    }
+                    <br/>
+                    {icons}
+                </td>
+                <td></td>
+            </tr>
+        });
+    }
+
     result
 }
 
@@ -539,5 +714,10 @@ fn main() {
     #[cfg(feature = "debug")]
     web_sys::console::log_1(&serde_wasm_bindgen::to_value("staring with debug").unwrap());
 
+    share::restore();
+    // the stored roll may reference monsters which no longer exist or are disabled
+    Dispatch::<SelectStore>::global()
+        .reduce_mut(|select| select.adjust_content(&Dispatch::<Settings>::global().get()));
+
     yew::Renderer::<App>::new().render();
 }
@@ -1,10 +1,36 @@
+use typed_i18n::TypedI18N;
+
+/// The language of the UI, the texts are in `msg.lrc`.
 #[derive(
-    Clone, Copy, Default, PartialEq, enum_tools::EnumTools, serde::Deserialize, serde::Serialize,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    enum_tools::EnumTools,
+    TypedI18N,
+    serde::Deserialize,
+    serde::Serialize,
 )]
 #[repr(usize)]
 #[enum_tools(as_str, iter, names)]
+#[typed_i18n(filename = "msg.lrc")]
+#[typed_i18n(builder = "mixed_str")]
 pub(crate) enum MsgLanguage {
     #[default]
     #[enum_tools(rename = "English")]
+    #[typed_i18n(name = "en")]
     English,
+    #[typed_i18n(name = "de")]
+    German,
+}
+
+impl MsgLanguage {
+    /// The code of the language, the same as the one of `GameLanguage`.
+    #[allow(dead_code)] // is only used by the roll binary
+    pub(crate) fn code(self) -> &'static str {
+        match self {
+            MsgLanguage::English => "en",
+            MsgLanguage::German => "de",
+        }
+    }
 }
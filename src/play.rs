@@ -0,0 +1,79 @@
+use crate::Settings;
+use crate::custom::SetupId;
+use crate::select::{Play, SelectStore};
+use yew::{Callback, Html, function_component, html};
+use yew_bootstrap::component::Button;
+use yew_bootstrap::icons::BI;
+use yew_bootstrap::util::Color;
+use yewdux::use_store;
+
+/// Steps through the doors of the chapter of the shown setup.
+#[function_component]
+pub(crate) fn ChapterPlay() -> Html {
+    let (settings, _) = use_store::<Settings>();
+    let (select, dispatch) = use_store::<SelectStore>();
+
+    let doors = select.chapter_doors();
+    let Some(position) = doors.iter().position(|setup| Some(*setup) == select.setup) else {
+        return html! {};
+    };
+    let is_opened = |setup: SetupId| select.opened.iter().any(|door| door.setup == setup);
+    let play = |setup: Option<SetupId>| {
+        setup.map_or_else(Callback::noop, |setup| {
+            dispatch.apply_callback(move |_| Play(setup))
+        })
+    };
+
+    let previous = position.checked_sub(1).map(|p| doors[p]);
+    let next = doors.get(position + 1).copied();
+
+    let back = html! {
+        <Button
+            style={Color::Secondary}
+            outline={true}
+            disabled={previous.is_none()}
+            onclick={play(previous)}
+        >
+            {BI::CHEVRON_LEFT}{" "}{settings.msg.back()}
+        </Button>
+    };
+
+    let entries = doors.iter().map(|setup| {
+        let setup = *setup;
+        let current = select.setup == Some(setup);
+        html! {
+            <Button
+                style={if current {Color::Primary} else {Color::Secondary}}
+                outline={!current}
+                onclick={dispatch.apply_callback(move |_| Play(setup))}
+            >
+                {if is_opened(setup) {BI::DOOR_OPEN} else {BI::DOOR_CLOSED}}{" "}
+                {setup.name(settings.game_language)}
+            </Button>
+        }
+    });
+
+    let forward = html! {
+        <Button
+            style={Color::Primary}
+            outline={next.is_some_and(is_opened)}
+            disabled={next.is_none()}
+            onclick={play(next)}
+        >
+            if next.is_some_and(is_opened) {
+                {settings.msg.next()}
+            } else {
+                {settings.msg.open_next_door()}
+            }
+            {" "}{BI::CHEVRON_RIGHT}
+        </Button>
+    };
+
+    html! {
+        <div class="btn-group flex-wrap mb-3" role="group">
+            {back}
+            {for entries}
+            {forward}
+        </div>
+    }
+}
@@ -0,0 +1,91 @@
+use crate::Settings;
+use crate::homebrew::HomebrewStore;
+use engine::game::AnyMonster;
+use std::rc::Rc;
+use yew::{Html, function_component, html};
+use yew_bootstrap::component::{Button, ButtonSize};
+use yew_bootstrap::icons::BI;
+use yew_bootstrap::util::Color;
+use yewdux::{Reducer, use_store};
+
+struct ToggleBan(AnyMonster);
+impl Reducer<Settings> for ToggleBan {
+    fn apply(self, mut rc_settings: Rc<Settings>) -> Rc<Settings> {
+        let settings = Rc::make_mut(&mut rc_settings);
+        if !settings.banned.remove(&self.0) {
+            settings.banned.insert(self.0);
+            settings.favorites.remove(&self.0);
+        }
+        rc_settings
+    }
+}
+
+struct ToggleFavorite(AnyMonster);
+impl Reducer<Settings> for ToggleFavorite {
+    fn apply(self, mut rc_settings: Rc<Settings>) -> Rc<Settings> {
+        let settings = Rc::make_mut(&mut rc_settings);
+        if !settings.favorites.remove(&self.0) {
+            settings.favorites.insert(self.0);
+            settings.banned.remove(&self.0);
+        }
+        rc_settings
+    }
+}
+
+#[function_component]
+pub(crate) fn MonsterPreferences() -> Html {
+    let (settings, dispatch) = use_store::<Settings>();
+    let (homebrew, _) = use_store::<HomebrewStore>();
+
+    let mut monsters = AnyMonster::iter(&homebrew.monsters)
+        .filter(|monster| homebrew.is_available(*monster, &settings.content))
+        .filter_map(|monster| {
+            let color = monster.color(&homebrew.monsters)?;
+            (!color.is_any_special()).then(|| {
+                let name = monster.name(settings.game_language, &homebrew.monsters);
+                (monster, color, name)
+            })
+        })
+        .collect::<Vec<_>>();
+    monsters.sort_by_key(|(_, _, name)| *name);
+
+    let monsters = monsters.into_iter().map(|(monster, color, name)| {
+        let banned = settings.banned.contains(&monster);
+        let favorite = settings.favorites.contains(&monster);
+        let toggle_ban = dispatch.apply_callback(move |_| ToggleBan(monster));
+        let toggle_favorite = dispatch.apply_callback(move |_| ToggleFavorite(monster));
+        html! {
+            <tr>
+                <td style={if banned {"text-decoration: line-through"} else {""}}>
+                    {color.short(settings.game_language)}{" - "}{name}
+                </td>
+                <td>
+                    <Button style={Color::Danger} outline={!banned} size={ButtonSize::Small} onclick={toggle_ban}>{BI::BAN}</Button>
+                    {" "}
+                    <Button style={Color::Warning} outline={!favorite} size={ButtonSize::Small} onclick={toggle_favorite}>
+                        {if favorite {BI::STAR_FILL} else {BI::STAR}}
+                    </Button>
+                </td>
+            </tr>
+        }
+    });
+
+    html! {
+        <details>
+            <summary>
+                {settings.msg.banned_and_favorites()}
+                if !settings.banned.is_empty() || !settings.favorites.is_empty() {
+                    {" ("}
+                    {settings.msg.banned_and_favorites_count(&settings.banned.len().to_string(), &settings.favorites.len().to_string())}
+                    {")"}
+                }
+            </summary>
+            <div>{settings.msg.banned_and_favorites_help()}</div>
+            <table class="table" style="width: auto">
+                <tbody>
+                    {for monsters}
+                </tbody>
+            </table>
+        </details>
+    }
+}
@@ -4,40 +4,84 @@
 #![allow(clippy::too_many_lines)]
 
 use crate::Settings;
-use crate::game::{Chapter, Color, Content, GameLanguage, Level, Monster, Number, SETUPS};
-use crate::setup::SetupItem;
-use rand::rng;
-use rand::seq::SliceRandom;
-#[cfg(feature = "debug")]
-use serde::Serialize;
+use crate::custom::{CustomSetups, SetupId};
+use crate::generate::DoorGenerator;
+use crate::history::History;
+use crate::homebrew::HomebrewStore;
+use crate::inventory::Inventory;
+use engine::game::{
+    AnyMonster, Chapter, Color, Content, GameLanguage, Level, Monster, Number, SETUPS,
+};
+use engine::roll::{Incomplete, Item, Missing, RollSettings, reroll, roll};
+use engine::setup::{RandomSlot, SetupItem, SetupKind};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng, rng};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 use yew::{Html, function_component, html};
-use yew_bootstrap::component::{Alert, Button};
+use yew_bootstrap::component::{Alert, Button, ButtonSize};
 use yew_bootstrap::icons::BI;
 use yewdux::mrc::Mrc;
 use yewdux::{Dispatch, Reducer, Store, use_store};
 
-#[cfg_attr(feature = "debug", derive(Debug, Serialize))]
-#[derive(Clone, PartialEq)]
-pub(crate) struct Item {
-    pub(crate) number: Number,
-    pub(crate) color: Color,
-    pub(crate) level: Level,
-    pub(crate) monster: Option<Monster>,
-    pub(crate) preset: bool,
+/// Why no monster could be drawn, as text.
+pub(crate) fn missing_text(missing: Missing, color: Color, settings: &Settings) -> String {
+    let color = color.name(settings.game_language);
+    match missing {
+        Missing::NotEnough => settings.msg.missing_not_enough(color),
+        Missing::Banned => settings.msg.missing_banned(color),
+        Missing::Miniature => settings.msg.missing_miniature(color),
+    }
+}
+
+/// A door of the played chapter which was already opened.
+#[cfg_attr(feature = "debug", derive(Debug))]
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+pub(crate) struct OpenedDoor {
+    pub(crate) setup: SetupId,
+    seed: u32,
+    #[serde(default)]
+    rerolls: u32,
+    output: Vec<Item>,
+}
+
+/// A random pick which is kept for all doors of a chapter.
+#[cfg_attr(feature = "debug", derive(Debug))]
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+pub(crate) struct StickyPick {
+    color: Color,
+    level: Level,
+    #[serde(deserialize_with = "engine::game::deserialize_any_monster")]
+    monster: Option<AnyMonster>,
+    /// the door which drew it first
+    setup: SetupId,
 }
 
-#[derive(Clone, PartialEq, Store)]
+#[derive(Clone, PartialEq, Deserialize, Serialize, Store)]
+#[store(storage = "local", storage_tab_sync)]
+#[serde(default)]
 pub(crate) struct SelectStore {
     // custom
-    selected: Mrc<Vec<SetupItem>>,
+    pub(crate) selected: Mrc<Vec<SetupItem>>,
     current_number: Number,
     current_color: Color,
+    /// any regular color instead of `current_color`
+    current_any_color: bool,
     current_level: Level,
-    current_monster: Mrc<Option<Monster>>,
+    /// the highest level, a random level if it's above `current_level`
+    current_max_level: Level,
     pub(crate) output: Mrc<Vec<Item>>,
-    pub(crate) setup: Option<(Content, Chapter, &'static str)>,
+    pub(crate) setup: Option<SetupId>,
+    pub(crate) seed: u32,
+    /// the rerolls since the roll, the seed of a reroll is derived from `seed` and it
+    pub(crate) rerolls: u32,
+    /// random (color, level) picks which are kept on randomize
+    pub(crate) locked: Vec<(Color, Level)>,
+    /// random picks of the current chapter (if sticky)
+    pub(crate) sticky: Vec<StickyPick>,
+    /// the doors of the current chapter opened so far
+    pub(crate) opened: Vec<OpenedDoor>,
 }
 
 impl Default for SelectStore {
@@ -47,10 +91,16 @@ impl Default for SelectStore {
             selected: Mrc::new(vec![]),
             current_number: Number::One,
             current_color: Color::White,
+            current_any_color: false,
             current_level: Level::Rookie,
-            current_monster: Mrc::default(),
+            current_max_level: Level::Rookie,
             output: Mrc::new(vec![]),
             setup: None,
+            seed: rng().random(),
+            rerolls: 0,
+            locked: Vec::new(),
+            sticky: Vec::new(),
+            opened: Vec::new(),
         }
     }
 }
@@ -66,8 +116,111 @@ impl SelectStore {
                 changed = true;
             }
         });
+        if self.setup.is_some_and(|setup| !setup.exists()) {
+            self.setup = None;
+        }
         if changed {
             self.output(Some(settings), false);
+            return;
+        }
+
+        // only the random monsters which no longer exist are rerolled (when restored from storage)
+        let homebrew = Dispatch::<HomebrewStore>::global().get();
+        let mut unavailable = false;
+        self.output.borrow_mut().iter_mut().for_each(|item| {
+            if let Some(monster) = item.monster
+                && !homebrew.is_available(monster, &settings.content)
+            {
+                item.monster = None;
+                unavailable = true;
+            }
+        });
+        // a monster without a reason is missing
+        let missing = |output: &[Item]| {
+            output
+                .iter()
+                .any(|item| item.monster.is_none() && item.missing.is_none())
+        };
+        if unavailable || missing(&self.output.borrow()) {
+            self.fill_empty(settings);
+        }
+        if missing(&self.output.borrow()) {
+            self.output(Some(settings), self.setup.is_some());
+        }
+    }
+
+    fn reseed(&mut self) {
+        self.seed = rng().random();
+    }
+
+    /// The settings of a roll, with the locked and sticky picks as fixed ones.
+    fn roll_settings(&mut self, settings: &Settings) -> RollSettings {
+        let homebrew = Dispatch::<HomebrewStore>::global().get();
+        let is_random = |co: Color, le: Level| {
+            self.selected
+                .borrow()
+                .iter()
+                .any(|item| item.monster.is_none() && item.choices().contains(&(co, le)))
+        };
+
+        // locked picks are kept
+        let locked = self
+            .locked
+            .iter()
+            .filter(|(co, le)| is_random(*co, *le))
+            .filter_map(|(co, le)| {
+                self.output
+                    .borrow()
+                    .iter()
+                    .find(|item| !item.preset && item.color == *co && item.level == *le)
+                    .and_then(|item| item.monster)
+                    .filter(|monster| homebrew.is_available(*monster, &settings.content))
+                    .map(|monster| ((*co, *le), monster))
+            })
+            .collect::<HashMap<_, _>>();
+        self.locked.retain(|key| locked.contains_key(key));
+        // sticky picks of the previous doors are kept as well, unless the door has them as preset
+        let preset = self
+            .selected
+            .borrow()
+            .iter()
+            .filter_map(|item| item.monster)
+            .map(|monster| AnyMonster::Game(monster.sculpt()))
+            .collect::<HashSet<_>>();
+        let fixed = self
+            .sticky
+            .iter()
+            .filter(|pick| settings.sticky && self.setup.is_some_and(|s| s != pick.setup))
+            .filter(|pick| {
+                pick.monster
+                    .is_none_or(|monster| !preset.contains(&monster.sculpt(&homebrew.monsters)))
+            })
+            .filter_map(|pick| {
+                pick.monster
+                    .map(|monster| ((pick.color, pick.level), monster))
+            })
+            .chain(locked)
+            .collect();
+
+        let recent = if settings.freshness {
+            Dispatch::<History>::global().get().recent(
+                self.setup.and_then(SetupId::key).as_ref(),
+                settings.freshness_rolls,
+                settings.freshness_chapters,
+            )
+        } else {
+            HashSet::new()
+        };
+
+        RollSettings {
+            content: settings.content.clone(),
+            banned: settings.banned.clone(),
+            favorites: settings.favorites.clone(),
+            recent,
+            fixed,
+            homebrew: homebrew.monsters.clone(),
+            copies: Dispatch::<Inventory>::global().get().copies(),
+            players: Some(settings.players),
         }
     }
 
@@ -75,84 +228,238 @@ impl SelectStore {
         if !keep_setup {
             self.setup = None;
         }
-        let mut o = self.output.borrow_mut();
         let rc_settings = Dispatch::<Settings>::global().get();
         let settings = settings.unwrap_or(&*rc_settings);
 
-        // gather available monsters (by type)
-        let avail_monsters = Monster::iter()
-            .filter(|monster| !monster.color().is_any_special())
-            .filter(|monster| settings.content.contains(&monster.content()))
-            .collect::<Vec<_>>();
+        let roll_settings = self.roll_settings(settings);
+        let mut rng = StdRng::seed_from_u64(u64::from(self.seed));
+        let output = roll(&roll_settings, &self.selected.borrow(), &mut rng)
+            .unwrap_or_else(|Incomplete(output)| output);
+        *self.output.borrow_mut() = output;
+        self.rerolls = 0;
 
-        let mut todo = HashMap::<Color, HashSet<Level>>::new();
-        for item in self.selected.borrow().iter() {
-            if item.monster.is_none() {
-                todo.entry(item.color).or_default().insert(item.level);
-            }
+        let o = self.output.borrow();
+        if settings.sticky {
+            Self::record_sticky(&mut self.sticky, self.setup, &o);
         }
+        Self::record_door(&mut self.opened, self.setup, self.seed, 0, &o);
+    }
 
-        o.clear();
-        let selected = Self::select(
-            avail_monsters
-                .iter()
-                .copied()
-                .filter(|m| {
-                    !self.selected.borrow().iter().any(|item| {
-                        item.monster == Some(*m)
-                            || item.monster.and_then(Monster::miniature) == Some(*m)
-                    })
-                })
-                .collect(),
-            &todo,
-        )
-        .or_else(|| Self::select(avail_monsters, &todo));
-        let Some(selected) = selected else {
+    /// Remembers the roll of the door, so it can be shown again.
+    fn record_door(
+        opened: &mut Vec<OpenedDoor>,
+        setup: Option<SetupId>,
+        seed: u32,
+        rerolls: u32,
+        output: &[Item],
+    ) {
+        let Some(setup) = setup else {
             return;
         };
-        for item in self.selected.borrow().iter() {
-            if !item.exclude {
-                if item.monster.is_none() {
-                    o.push(Item {
-                        number: item.number,
-                        color: item.color,
-                        level: item.level,
-                        monster: selected.get(&(item.color, item.level)).copied(),
-                        preset: false,
-                    });
-                } else {
-                    o.push(Item {
-                        number: item.number,
-                        color: item.color,
-                        level: item.level,
-                        monster: item.monster,
-                        preset: true,
-                    });
-                }
+        let door = OpenedDoor {
+            setup,
+            seed,
+            rerolls,
+            output: output.to_vec(),
+        };
+        match opened.iter_mut().find(|door| door.setup == setup) {
+            Some(opened_door) => *opened_door = door,
+            None => opened.push(door),
+        }
+    }
+
+    /// Remembers the random picks of the door for the following doors of the chapter, a pick is
+    /// only changed by the door which picked it first.
+    fn record_sticky(sticky: &mut Vec<StickyPick>, setup: Option<SetupId>, output: &[Item]) {
+        let Some(setup) = setup else {
+            return;
+        };
+        for item in output.iter().filter(|item| !item.preset) {
+            let Some(monster) = item.monster else {
+                continue;
+            };
+            match sticky
+                .iter_mut()
+                .find(|pick| pick.color == item.color && pick.level == item.level)
+            {
+                Some(pick) if pick.setup == setup => pick.monster = Some(monster),
+                Some(_) => {}
+                None => sticky.push(StickyPick {
+                    color: item.color,
+                    level: item.level,
+                    monster: Some(monster),
+                    setup,
+                }),
             }
         }
     }
 
-    fn select(
-        mut avail: Vec<Monster>,
-        todo: &HashMap<Color, HashSet<Level>>,
-    ) -> Option<HashMap<(Color, Level), Monster>> {
-        avail.shuffle(&mut rng());
+    /// Adds the roll to the history, only rolls of the user are recorded.
+    fn record(&self) {
+        let monsters = self
+            .output
+            .borrow()
+            .iter()
+            .filter_map(|item| item.monster)
+            .collect::<HashSet<_>>();
+        if !monsters.is_empty() {
+            let setup = self.setup;
+            Dispatch::<History>::global().reduce_mut(|history| {
+                history.record(
+                    setup.and_then(SetupId::key),
+                    setup.and_then(SetupId::chapter),
+                    monsters,
+                );
+            });
+        }
+    }
 
-        let mut r = HashMap::new();
-        for (co, levels) in todo {
-            for le in levels {
-                let (i, m) = avail
-                    .iter()
-                    .copied()
-                    .enumerate()
-                    .find(|(_, m)| m.color() == *co)?;
-                avail.swap_remove(i);
-                r.insert((*co, *le), m);
+    /// Replaces the random monster of one (color, level) with another one.
+    fn reroll(&mut self, color: Color, level: Level) {
+        let mut rng = self.reroll_rng();
+        let rc_settings = Dispatch::<Settings>::global().get();
+        if !self.replace(color, level, &rc_settings, &mut rng) {
+            return;
+        }
+        self.record();
+        if rc_settings.sticky {
+            Self::record_sticky(&mut self.sticky, self.setup, &self.output.borrow());
+        }
+        Self::record_door(
+            &mut self.opened,
+            self.setup,
+            self.seed,
+            self.rerolls,
+            &self.output.borrow(),
+        );
+    }
+
+    /// The generator of the next reroll, the same seed rerolls the same.
+    fn reroll_rng(&mut self) -> StdRng {
+        self.rerolls += 1;
+        StdRng::seed_from_u64((u64::from(self.seed) << 32) | u64::from(self.rerolls))
+    }
+
+    /// Draws monsters for the random items without one, e.g. the homebrew monsters of a link.
+    pub(crate) fn fill_empty(&mut self, settings: &Settings) {
+        let mut empty = self
+            .output
+            .borrow()
+            .iter()
+            .filter(|item| !item.preset && item.monster.is_none())
+            .map(|item| (item.color, item.level))
+            .collect::<Vec<_>>();
+        empty.dedup();
+        let mut rng = self.reroll_rng();
+        for (color, level) in empty {
+            self.replace(color, level, settings, &mut rng);
+        }
+    }
+
+    /// Draws another monster for the random items of the (color, level), `false` if there is none.
+    fn replace<R: Rng + ?Sized>(
+        &mut self,
+        color: Color,
+        level: Level,
+        settings: &Settings,
+        rng: &mut R,
+    ) -> bool {
+        let roll_settings = self.roll_settings(settings);
+        let Some(monster) = reroll(
+            &roll_settings,
+            &self.selected.borrow(),
+            &self.output.borrow(),
+            color,
+            level,
+            rng,
+        ) else {
+            return false;
+        };
+
+        for item in self.output.borrow_mut().iter_mut() {
+            if !item.preset && item.color == color && item.level == level {
+                item.monster = Some(monster);
+                item.missing = None;
             }
         }
+        true
+    }
+
+    /// Rolls the monsters of a preset or imported setup.
+    fn show(&mut self, setup: SetupId) {
+        let chapter = setup.chapter();
+        let same_chapter = |other: SetupId| other.chapter() == chapter;
+        self.locked.clear();
+        // the sticky picks and opened doors are only kept within a chapter
+        if self
+            .sticky
+            .first()
+            .is_some_and(|pick| !same_chapter(pick.setup))
+        {
+            self.sticky.clear();
+        }
+        if self
+            .opened
+            .first()
+            .is_some_and(|door| !same_chapter(door.setup))
+        {
+            self.opened.clear();
+        }
+        {
+            let mut selected = self.selected.borrow_mut();
+            selected.clear();
+            selected.extend(setup.monsters());
+        }
+        self.reseed();
+        self.setup = Some(setup);
+        self.output(None, true);
+        self.record();
+    }
+
+    /// Shows a door of the played chapter again, or opens it if it wasn't opened yet.
+    fn play(&mut self, setup: SetupId) {
+        let Some(door) = self.opened.iter().find(|door| door.setup == setup).cloned() else {
+            self.show(setup);
+            return;
+        };
+        self.locked.clear();
+        {
+            let mut selected = self.selected.borrow_mut();
+            selected.clear();
+            selected.extend(setup.monsters());
+        }
+        self.seed = door.seed;
+        self.rerolls = door.rerolls;
+        self.setup = Some(setup);
+        *self.output.borrow_mut() = door.output;
+    }
+
+    /// The doors of the chapter of the shown setup.
+    pub(crate) fn chapter_doors(&self) -> Vec<SetupId> {
+        let Some((content, chapter)) = self.setup.and_then(SetupId::chapter) else {
+            return Vec::new();
+        };
+        chapter_setups(content, chapter)
+    }
+
+    /// Forgets the shown setup, the opened doors and the sticky picks of imported setups.
+    fn forget_custom(&mut self) {
+        let is_custom = |setup: SetupId| setup.preset().is_none();
+        if self.setup.is_some_and(is_custom) {
+            self.setup = None;
+        }
+        self.opened.retain(|door| !is_custom(door.setup));
+        self.sticky.retain(|pick| !is_custom(pick.setup));
+    }
 
-        Some(r)
+    /// How the color and level of the next random slot are drawn, `None` if they are selected.
+    fn current_random(&self) -> Option<RandomSlot> {
+        let random_level =
+            self.current_max_level > self.current_level && self.current_color != Color::Commander;
+        (self.current_any_color || random_level).then_some(RandomSlot {
+            any_color: self.current_any_color,
+            max_level: self.current_max_level.max(self.current_level),
+        })
     }
 
     pub(crate) fn remove_excluded(&mut self) {
@@ -172,6 +479,17 @@ impl Reducer<SelectStore> for Color {
     fn apply(self, mut rc_state: Rc<SelectStore>) -> Rc<SelectStore> {
         let state = Rc::make_mut(&mut rc_state);
         state.current_color = self;
+        state.current_any_color = false;
+        rc_state
+    }
+}
+
+/// Selects any regular color for the next random slot.
+struct AnyColor;
+impl Reducer<SelectStore> for AnyColor {
+    fn apply(self, mut rc_state: Rc<SelectStore>) -> Rc<SelectStore> {
+        let state = Rc::make_mut(&mut rc_state);
+        state.current_any_color = true;
         rc_state
     }
 }
@@ -180,6 +498,17 @@ impl Reducer<SelectStore> for Level {
     fn apply(self, mut rc_state: Rc<SelectStore>) -> Rc<SelectStore> {
         let state = Rc::make_mut(&mut rc_state);
         state.current_level = self;
+        state.current_max_level = self;
+        rc_state
+    }
+}
+
+/// Selects the highest level for the next random slot.
+struct MaxLevel(Level);
+impl Reducer<SelectStore> for MaxLevel {
+    fn apply(self, mut rc_state: Rc<SelectStore>) -> Rc<SelectStore> {
+        let state = Rc::make_mut(&mut rc_state);
+        state.current_max_level = self.0;
         rc_state
     }
 }
@@ -187,13 +516,32 @@ impl Reducer<SelectStore> for Level {
 impl Reducer<SelectStore> for Option<Monster> {
     fn apply(self, mut rc_state: Rc<SelectStore>) -> Rc<SelectStore> {
         let state = Rc::make_mut(&mut rc_state);
+        let random = state.current_random().filter(|_| self.is_none());
         state.selected.borrow_mut().push(SetupItem {
             number: state.current_number,
-            color: state.current_color,
+            color: if random.is_some_and(|random| random.any_color) {
+                Color::White
+            } else {
+                state.current_color
+            },
             level: state.current_level,
             monster: self,
             exclude: false,
+            random,
         });
+        state.reseed();
+        state.output(None, false);
+        rc_state
+    }
+}
+
+/// Replaces the custom setup, e.g. with a generated one.
+pub(crate) struct SetSelected(pub(crate) Vec<SetupItem>);
+impl Reducer<SelectStore> for SetSelected {
+    fn apply(self, mut rc_state: Rc<SelectStore>) -> Rc<SelectStore> {
+        let state = Rc::make_mut(&mut rc_state);
+        *state.selected.borrow_mut() = self.0;
+        state.reseed();
         state.output(None, false);
         rc_state
     }
@@ -203,7 +551,90 @@ pub(crate) struct Randomize;
 impl Reducer<SelectStore> for Randomize {
     fn apply(self, mut rc_state: Rc<SelectStore>) -> Rc<SelectStore> {
         let state = Rc::make_mut(&mut rc_state);
+        state.reseed();
+        state.output(None, true);
+        state.record();
+        rc_state
+    }
+}
+
+/// Rolls with the same seed, e.g. with other settings.
+pub(crate) struct RollAgain;
+impl Reducer<SelectStore> for RollAgain {
+    fn apply(self, mut rc_state: Rc<SelectStore>) -> Rc<SelectStore> {
+        let state = Rc::make_mut(&mut rc_state);
+        state.output(None, true);
+        state.record();
+        rc_state
+    }
+}
+
+pub(crate) struct Reroll(pub(crate) Color, pub(crate) Level);
+impl Reducer<SelectStore> for Reroll {
+    fn apply(self, mut rc_state: Rc<SelectStore>) -> Rc<SelectStore> {
+        let state = Rc::make_mut(&mut rc_state);
+        state.reroll(self.0, self.1);
+        rc_state
+    }
+}
+
+pub(crate) struct ToggleLock(pub(crate) Color, pub(crate) Level);
+impl Reducer<SelectStore> for ToggleLock {
+    fn apply(self, mut rc_state: Rc<SelectStore>) -> Rc<SelectStore> {
+        let state = Rc::make_mut(&mut rc_state);
+        let key = (self.0, self.1);
+        if state.locked.contains(&key) {
+            state.locked.retain(|k| *k != key);
+        } else {
+            state.locked.push(key);
+        }
+        rc_state
+    }
+}
+
+struct ResetSticky;
+impl Reducer<SelectStore> for ResetSticky {
+    fn apply(self, mut rc_state: Rc<SelectStore>) -> Rc<SelectStore> {
+        let state = Rc::make_mut(&mut rc_state);
+        state.sticky.clear();
+        rc_state
+    }
+}
+
+struct ToggleSticky;
+impl Reducer<Settings> for ToggleSticky {
+    fn apply(self, mut rc_settings: Rc<Settings>) -> Rc<Settings> {
+        let settings = Rc::make_mut(&mut rc_settings);
+        settings.sticky = !settings.sticky;
+        rc_settings
+    }
+}
+
+pub(crate) struct Play(pub(crate) SetupId);
+impl Reducer<SelectStore> for Play {
+    fn apply(self, mut rc_state: Rc<SelectStore>) -> Rc<SelectStore> {
+        let state = Rc::make_mut(&mut rc_state);
+        state.play(self.0);
+        rc_state
+    }
+}
+
+pub(crate) struct Seed(pub(crate) u32);
+impl Reducer<SelectStore> for Seed {
+    fn apply(self, mut rc_state: Rc<SelectStore>) -> Rc<SelectStore> {
+        let state = Rc::make_mut(&mut rc_state);
+        state.seed = self.0;
         state.output(None, true);
+        state.record();
+        rc_state
+    }
+}
+
+pub(crate) struct ForgetCustom;
+impl Reducer<SelectStore> for ForgetCustom {
+    fn apply(self, mut rc_state: Rc<SelectStore>) -> Rc<SelectStore> {
+        let state = Rc::make_mut(&mut rc_state);
+        state.forget_custom();
         rc_state
     }
 }
@@ -213,6 +644,7 @@ impl Reducer<SelectStore> for Remove {
     fn apply(self, mut rc_state: Rc<SelectStore>) -> Rc<SelectStore> {
         let state = Rc::make_mut(&mut rc_state);
         state.selected.borrow_mut().remove(self.0);
+        state.reseed();
         state.output(None, false);
         rc_state
     }
@@ -220,8 +652,10 @@ impl Reducer<SelectStore> for Remove {
 
 enum Preset {
     Content(Content),
+    /// the imported setups
+    Custom,
     Chapter(Chapter),
-    Show(usize),
+    Show(SetupId),
 }
 
 impl Reducer<SelectStore> for Preset {
@@ -231,6 +665,12 @@ impl Reducer<SelectStore> for Preset {
             Preset::Content(c) => {
                 Dispatch::<Settings>::global().reduce_mut(|settings| {
                     settings.preset_content = c;
+                    settings.preset_custom = false;
+                });
+            }
+            Preset::Custom => {
+                Dispatch::<Settings>::global().reduce_mut(|settings| {
+                    settings.preset_custom = true;
                 });
             }
             Preset::Chapter(ct) => {
@@ -238,45 +678,70 @@ impl Reducer<SelectStore> for Preset {
                     settings.preset_chapter = ct;
                 });
             }
-            Preset::Show(index) => {
-                let rc_settings = Dispatch::<Settings>::global().get();
-                if let Some(setup) = SETUPS
-                    .iter()
-                    .filter(|s| {
-                        s.content == rc_settings.preset_content
-                            && s.chapter == rc_settings.preset_chapter
-                    })
-                    .nth(index)
-                {
-                    {
-                        let mut selected = state.selected.borrow_mut();
-                        selected.clear();
-                        for item in setup.monsters {
-                            selected.push(item.clone());
-                        }
-                    }
-                    state.output(None, false);
-                    let rc_settings = Dispatch::<Settings>::global().get();
-                    state.setup = Some((
-                        setup.content,
-                        setup.chapter,
-                        setup.name(rc_settings.game_language),
-                    ));
-                }
+            Preset::Show(setup) => {
+                state.show(setup);
             }
         }
         rc_state
     }
 }
 
+/// The setups of a chapter (of the imported setups if `content` is `None`), ordered by their kind
+/// and without empty setups.
+fn chapter_setups(content: Option<Content>, chapter: Chapter) -> Vec<SetupId> {
+    let mut setups = match content {
+        Some(content) => SETUPS
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| (s.content, s.chapter) == (content, chapter))
+            .filter(|(_, s)| s.kind != SetupKind::Setup || !s.monsters.is_empty())
+            .map(|(index, s)| (SetupId::Preset(index), s.kind))
+            .collect::<Vec<_>>(),
+        None => Dispatch::<CustomSetups>::global()
+            .get()
+            .setups
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.chapter == chapter)
+            .filter(|(_, s)| s.kind != SetupKind::Setup || !s.monsters.is_empty())
+            .map(|(custom, s)| (SetupId::Custom { custom }, s.kind))
+            .collect(),
+    };
+    setups.sort_by_key(|(_, kind)| *kind);
+    setups.into_iter().map(|(setup, _)| setup).collect()
+}
+
+pub(crate) fn render_missing(settings: &Settings, store: &SelectStore) -> Html {
+    let mut missing = Vec::new();
+    for item in store.output.borrow().iter() {
+        if let Some(reason) = item.missing
+            && !missing.contains(&(item.color, reason))
+        {
+            missing.push((item.color, reason));
+        }
+    }
+    missing
+        .into_iter()
+        .map(|(color, reason)| {
+            html! {
+                <Alert style={yew_bootstrap::util::Color::Warning}>
+                    {missing_text(reason, color, settings)}
+                </Alert>
+            }
+        })
+        .collect()
+}
+
 #[function_component]
 pub(crate) fn Select() -> Html {
-    let (settings, _) = use_store::<Settings>();
+    let (settings, settings_dispatch) = use_store::<Settings>();
     let (store, dispatch) = use_store::<SelectStore>();
+    let (custom, _) = use_store::<CustomSetups>();
 
     if settings.preset {
+        let show_custom = settings.preset_custom && !custom.setups.is_empty();
         let mut contents = SETUPS.iter().map(|s| s.content).collect::<Vec<_>>();
-        contents.sort_by_key(|s| s.order_name(settings.game_language));
+        contents.sort_by_key(|s| (s.order(), s.name(settings.game_language)));
         contents.dedup();
         let contents = contents.into_iter().map(|c| {
             let onclick = dispatch.apply_callback(move |_| Preset::Content(c));
@@ -289,19 +754,39 @@ pub(crate) fn Select() -> Html {
                         name="preset_content"
                         id={id.clone()}
                         autocomplete="off"
-                        checked={settings.preset_content == c}
+                        checked={!show_custom && settings.preset_content == c}
                         onclick={onclick}
                     />
                     <label class="btn btn-outline-primary" for={id}>{c.name(settings.game_language)}</label>
                 </>
             }
         });
+        let custom_content = (!custom.setups.is_empty()).then(|| {
+            html! {
+                <>
+                    <input
+                        type="radio"
+                        class="btn-check"
+                        name="preset_content"
+                        id="preset_content_custom"
+                        autocomplete="off"
+                        checked={show_custom}
+                        onclick={dispatch.apply_callback(|_| Preset::Custom)}
+                    />
+                    <label class="btn btn-outline-primary" for="preset_content_custom">{settings.msg.custom()}</label>
+                </>
+            }
+        });
 
-        let mut chapters = SETUPS
-            .iter()
-            .filter(|s| s.content == settings.preset_content)
-            .map(|s| s.chapter)
-            .collect::<Vec<_>>();
+        let mut chapters = if show_custom {
+            custom.setups.iter().map(|s| s.chapter).collect::<Vec<_>>()
+        } else {
+            SETUPS
+                .iter()
+                .filter(|s| s.content == settings.preset_content)
+                .map(|s| s.chapter)
+                .collect::<Vec<_>>()
+        };
         chapters.sort();
         chapters.dedup();
         let content_types = chapters.into_iter().map(|ct| {
@@ -323,38 +808,80 @@ pub(crate) fn Select() -> Html {
             }
         });
 
-        let entries = SETUPS
-            .iter()
-            .filter(|s| {
-                s.content == settings.preset_content && s.chapter == settings.preset_chapter
-            })
-            .collect::<Vec<_>>();
-        let entries = entries.into_iter().enumerate().map(|(i, setup)| {
-            let onclick = dispatch.apply_callback(move |_| Preset::Show(i));
+        let entries = chapter_setups(
+            (!show_custom).then_some(settings.preset_content),
+            settings.preset_chapter,
+        );
+        // the setups, the doors and the pages each in an own group
+        let groups = [
+            |kind| kind == SetupKind::Setup,
+            |kind| matches!(kind, SetupKind::Door(_) | SetupKind::NamedDoor),
+            |kind| matches!(kind, SetupKind::Page(_)),
+        ]
+        .map(|is_in_group: fn(SetupKind) -> bool| {
+            entries
+                .iter()
+                .copied()
+                .filter(|setup| setup.kind().is_some_and(is_in_group))
+                .map(|setup| {
+                    let onclick = dispatch.apply_callback(move |_| Preset::Show(setup));
+                    html! {
+                        <button
+                            class="btn btn-primary"
+                            type="button"
+                            onclick={onclick}
+                            data-bs-toggle="collapse"
+                            data-bs-target="#collapseThree"
+                        >
+                            if let Some(SetupKind::Page(_)) = setup.kind() {
+                                {BI::BOOK}{" "}
+                            }
+                            {setup.name(settings.game_language)}
+                        </button>
+                    }
+                })
+                .collect::<Vec<_>>()
+        });
+        let groups = groups.into_iter().filter(|group| !group.is_empty()).map(|group| {
             html! {
-                <>
-                    <button
-                        class="btn btn-primary"
-                        type="button"
-                        onclick={onclick}
-                        data-bs-toggle="collapse"
-                        data-bs-target="#collapseThree"
-                    >
-                        {setup.name(settings.game_language)}
-                    </button>
-                </>
+                <div class="btn-group-vertical" style="vertical-align: top" role="group" aria-label="Vertical button group">
+                    {for group}
+                </div>
             }
         });
         html! {
             <div>
             <div class="btn-group-vertical" style="vertical-align: top" role="group" aria-label="Vertical button group">
                 {for contents}
+                {for custom_content}
             </div>
             <div class="btn-group-vertical" style="vertical-align: top" role="group" aria-label="Vertical button group">
                 {for content_types}
             </div>
-            <div class="btn-group-vertical" style="vertical-align: top" role="group" aria-label="Vertical button group">
-                {for entries}
+            {for groups}
+            <div class="form-check">
+              <input
+                type="checkbox"
+                class="form-check-input"
+                value=""
+                id="sticky"
+                checked={settings.sticky}
+                onchange={settings_dispatch.apply_callback(|_| ToggleSticky)}
+              />
+              <label class="form-check-label" for="sticky">
+                {settings.msg.sticky()}
+              </label>
+              if settings.sticky && !store.sticky.is_empty() {
+                {" "}
+                <Button
+                    style={yew_bootstrap::util::Color::Secondary}
+                    outline={true}
+                    size={ButtonSize::Small}
+                    onclick={dispatch.apply_callback(|_| ResetSticky)}
+                >
+                    {settings.msg.sticky_reset(&store.sticky.len().to_string())}
+                </Button>
+              }
             </div>
             </div>
         }
@@ -380,6 +907,39 @@ pub(crate) fn Select() -> Html {
                             </td>
                         </tr>
                     }
+                } else if let Some(random) = item.random {
+                    html! {
+                        <tr>
+                            <td>
+                                <strong>
+                                    if random.any_color {
+                                        {"?"}
+                                    } else {
+                                        {item.color.prefix(settings.game_language)}
+                                    }
+                                    {item.number.as_str()}
+                                </strong>
+                            </td>
+                            <td>
+                                if random.any_color {
+                                    {settings.msg.any_color()}
+                                } else {
+                                    {item.color.short(settings.game_language)}
+                                }
+                                {" - "}{item.level.name(settings.game_language)}
+                                if random.max_level > item.level {
+                                    {" "}{settings.msg.up_to(random.max_level.name(settings.game_language))}
+                                }
+                            </td>
+                            <td>
+                                <Button
+                                    style={yew_bootstrap::util::Color::Danger}
+                                    outline={true}
+                                    onclick={trash_onclick}
+                                >{BI::TRASH}</Button>
+                            </td>
+                        </tr>
+                    }
                 } else {
                     html! {
                         <tr>
@@ -432,13 +992,14 @@ pub(crate) fn Select() -> Html {
                         name="color"
                         id={id.clone()}
                         autocomplete="off"
-                        checked={store.current_color == color}
+                        checked={store.current_color == color && !store.current_any_color}
                         onchange={onchange}
                     />
                     <label class="btn btn-outline-primary" for={id}>{color.name(settings.game_language)}</label>
                 </>
             }
         });
+        let is_commander = store.current_color == Color::Commander && !store.current_any_color;
 
         let levels = [Level::Rookie, Level::Fighter, Level::Veteran, Level::Champion]
             .into_iter()
@@ -453,19 +1014,44 @@ pub(crate) fn Select() -> Html {
                             name="level"
                             id={id.clone()}
                             autocomplete="off"
-                            checked={store.current_level == level && store.current_color != Color::Commander}
+                            checked={store.current_level == level && !is_commander}
                             onchange={onchange}
-                            disabled={store.current_color == Color::Commander}
+                            disabled={is_commander}
                         />
                         <label class="btn btn-outline-primary" for={id}>{level.name(settings.game_language)}</label>
                     </>
                 }
             });
+        let max_levels = [Level::Rookie, Level::Fighter, Level::Veteran, Level::Champion]
+            .into_iter()
+            .map(|level| {
+                let id = format!("max_level:{}", level.id());
+                let onchange = dispatch.apply_callback(move |_| MaxLevel(level));
+                html! {
+                    <>
+                        <input
+                            type="radio"
+                            class="btn-check"
+                            name="max_level"
+                            id={id.clone()}
+                            autocomplete="off"
+                            checked={store.current_max_level == level && !is_commander}
+                            onchange={onchange}
+                            disabled={is_commander || level < store.current_level}
+                        />
+                        <label class="btn btn-outline-primary" for={id}>{settings.msg.up_to(level.name(settings.game_language))}</label>
+                    </>
+                }
+            });
+
+        // a monster can only be selected if the color and level are
+        let is_random = store.current_random().is_some();
 
         let mut monsters = Monster::iter()
             .filter(|monster| !monster.color().is_any_special())
             .filter(|monster| {
-                monster.color() == store.current_color
+                !is_random
+                    && monster.color() == store.current_color
                     && settings.content.contains(&monster.content())
             })
             .map(|monster| (monster, monster.name(settings.game_language)))
@@ -493,31 +1079,41 @@ pub(crate) fn Select() -> Html {
                     {for list}
                 </tbody>
             </table>
-                if !store.selected.borrow().is_empty() && store.output.borrow().is_empty() {
-                    <Alert style={yew_bootstrap::util::Color::Warning}>
-                        {"Too many different monsters requested"}
-                    </Alert>
-                }
+                {render_missing(&settings, &store)}
             <div>
                 <div class="btn-group-vertical" style="vertical-align: top" role="group" aria-label="Vertical button group">
                     {for numbers}
                 </div>
                 <div class="btn-group-vertical" style="vertical-align: top" role="group" aria-label="Vertical button group">
                     {for colors}
+                    <input
+                        type="radio"
+                        class="btn-check"
+                        name="color"
+                        id="color:any"
+                        autocomplete="off"
+                        checked={store.current_any_color}
+                        onchange={dispatch.apply_callback(|_| AnyColor)}
+                    />
+                    <label class="btn btn-outline-primary" for="color:any">{settings.msg.any_color()}</label>
                 </div>
                 <div class="btn-group-vertical" style="vertical-align: top" role="group" aria-label="Vertical button group">
                     {for levels}
                 </div>
+                <div class="btn-group-vertical" style="vertical-align: top" role="group" aria-label="Vertical button group">
+                    {for max_levels}
+                </div>
                 <div class="btn-group-vertical" style="vertical-align: top" role="group" aria-label="Vertical button group">
                     <Button
                         style={yew_bootstrap::util::Color::Success}
                         onclick={dispatch.apply_callback(|_|Option::<Monster>::None)}
                     >
-                        {"Random"}
+                        {settings.msg.random()}
                     </Button>
                     {for monsters}
                 </div>
             </div>
+            <DoorGenerator/>
         </>
         }
     }
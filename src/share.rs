@@ -0,0 +1,383 @@
+//! The current setup encoded in the URL fragment, so that a link opens the same setup.
+//!
+//! The fragment consists of `key=value` pairs separated by `&`:
+//! - `c`: the enabled content, separated by `.`, e.g. `Core.Apocalypse`
+//! - `p`: the number of players
+//! - `m`: `p` for preset and `c` for custom monster setups
+//! - `s`: the seed
+//! - `r`: the rerolls since the roll (optional)
+//! - `u`: the shown preset as `<content>.<chapter>.<kind>.<label>` with the English label, e.g.
+//!   `Core.3.d2.` (optional)
+//! - `l`: the selected items, separated by `.`
+//! - `o`: the rolled items, separated by `.`
+//!
+//! An item is `<number><color><level><monster><flag>`, e.g. `1wroSkeleton%20Archer*`, where the
+//! monster is the optional English name and the flag is `!` for excluded resp. `*` for preset
+//! monsters. Homebrew monsters are left out, they only exist on the device which created them, and
+//! are drawn again when the link is opened. A selected item with a random color has the color `a`
+//! and one with a random level has `~` and the highest level after the level, e.g. `2aro~ve`.
+//!
+//! Text is percent-encoded, with all but ASCII letters and digits encoded. Unknown keys are
+//! ignored.
+
+use crate::Settings;
+use crate::custom::SetupId;
+use crate::select::SelectStore;
+use core::fmt::Write;
+use engine::game::{
+    AnyMonster, Chapter, Color, Content, GameLanguage, Level, Monster, Number, SETUPS,
+};
+use engine::roll::Item;
+use engine::setup::{RandomSlot, SetupItem, SetupKey, SetupKind};
+use std::collections::HashSet;
+use web_sys::wasm_bindgen::JsValue;
+use yewdux::Dispatch;
+
+struct Shared {
+    content: HashSet<Content>,
+    players: Number,
+    preset: bool,
+    seed: u32,
+    rerolls: u32,
+    setup: Option<usize>,
+    selected: Vec<SetupItem>,
+    output: Vec<Item>,
+}
+
+pub(crate) fn link(settings: &Settings, select: &SelectStore) -> String {
+    let mut content = settings
+        .content
+        .iter()
+        .map(|content| content.as_str())
+        .collect::<Vec<_>>();
+    content.sort_unstable();
+    let mut link = format!(
+        "#c={}&p={}&m={}&s={}",
+        content.join("."),
+        settings.players.as_str(),
+        if settings.preset { "p" } else { "c" },
+        select.seed,
+    );
+    if select.rerolls > 0 {
+        let _ = write!(link, "&r={}", select.rerolls);
+    }
+    if let Some(key) = select.setup.and_then(SetupId::key) {
+        write_setup(&mut link, &key);
+    }
+    link.push_str("&l=");
+    for (i, item) in select.selected.borrow().iter().enumerate() {
+        if i > 0 {
+            link.push('.');
+        }
+        write_item(
+            &mut link,
+            item.number,
+            item.color,
+            item.level,
+            item.random,
+            item.monster.map(AnyMonster::Game),
+            item.exclude.then_some('!'),
+        );
+    }
+    link.push_str("&o=");
+    for (i, item) in select.output.borrow().iter().enumerate() {
+        if i > 0 {
+            link.push('.');
+        }
+        write_item(
+            &mut link,
+            item.number,
+            item.color,
+            item.level,
+            None,
+            item.monster,
+            item.preset.then_some('*'),
+        );
+    }
+    link
+}
+
+/// Restores the setup from the URL fragment (if there is one) and removes the fragment.
+pub(crate) fn restore() {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let location = window.location();
+    let Some(shared) = location
+        .hash()
+        .ok()
+        .as_deref()
+        .and_then(|hash| hash.strip_prefix('#'))
+        .and_then(decode)
+    else {
+        return;
+    };
+
+    Dispatch::<Settings>::global().reduce_mut(|settings| {
+        settings.preset = shared.preset && shared.content.contains(&Content::Core);
+        settings.content = shared.content;
+        settings.players = shared.players;
+        if let Some(setup) = shared.setup.and_then(|index| SETUPS.get(index)) {
+            settings.preset_content = setup.content;
+            settings.preset_custom = false;
+            settings.preset_chapter = setup.chapter;
+        }
+    });
+    Dispatch::<SelectStore>::global().reduce_mut(|select| {
+        *select.selected.borrow_mut() = shared.selected;
+        *select.output.borrow_mut() = shared.output;
+        select.seed = shared.seed;
+        select.rerolls = shared.rerolls;
+        select.setup = shared.setup.map(SetupId::Preset);
+        select.fill_empty(&Dispatch::<Settings>::global().get());
+    });
+
+    if let Ok(history) = window.history() {
+        let url = format!(
+            "{}{}",
+            location.pathname().unwrap_or_default(),
+            location.search().unwrap_or_default()
+        );
+        let _ = history.replace_state_with_url(&JsValue::NULL, "", Some(&url));
+    }
+}
+
+fn decode(fragment: &str) -> Option<Shared> {
+    let mut content = None;
+    let mut players = None;
+    let mut preset = None;
+    let mut seed = None;
+    let mut rerolls = 0;
+    let mut setup = None;
+    let mut selected = None;
+    let mut output = None;
+
+    for pair in fragment.split('&') {
+        let (key, value) = pair.split_once('=')?;
+        match key {
+            "c" => {
+                // unknown content (of a newer version) is ignored
+                let names = value.split('.').collect::<Vec<_>>();
+                content = Some(
+                    Content::iter()
+                        .filter(|content| names.contains(&content.as_str()))
+                        .collect::<HashSet<_>>(),
+                );
+            }
+            "p" => players = Some(read_number(value)?),
+            "m" => {
+                preset = Some(match value {
+                    "p" => true,
+                    "c" => false,
+                    _ => return None,
+                });
+            }
+            "s" => seed = Some(value.parse().ok()?),
+            "r" => rerolls = value.parse().ok()?,
+            // a setup which doesn't exist (yet) isn't shown
+            "u" => setup = read_setup(value)?.index(),
+            "l" => {
+                selected = Some(
+                    read_list(value, '!')?
+                        .into_iter()
+                        .map(
+                            |(number, color, level, random, monster, exclude)| SetupItem {
+                                number,
+                                color,
+                                level,
+                                monster,
+                                exclude,
+                                random,
+                            },
+                        )
+                        .collect(),
+                );
+            }
+            "o" => {
+                output = Some(
+                    read_list(value, '*')?
+                        .into_iter()
+                        .map(|(number, color, level, _, monster, preset)| Item {
+                            number,
+                            color,
+                            level,
+                            monster: monster.map(AnyMonster::Game),
+                            preset,
+                            missing: None,
+                        })
+                        .collect(),
+                );
+            }
+            // e.g. from a newer version, only malformed values of known keys reject the link
+            _ => {}
+        }
+    }
+
+    Some(Shared {
+        content: content?,
+        players: players?,
+        preset: preset?,
+        seed: seed?,
+        rerolls,
+        setup,
+        selected: selected?,
+        output: output?,
+    })
+}
+
+fn write_setup(link: &mut String, key: &SetupKey) {
+    let _ = write!(link, "&u={}.{}.", key.content.as_str(), key.chapter.0);
+    let _ = match key.kind {
+        SetupKind::Setup => write!(link, "s"),
+        SetupKind::Door(door) => write!(link, "d{door}"),
+        SetupKind::NamedDoor => write!(link, "n"),
+        SetupKind::Page(page) => write!(link, "p{page}"),
+    };
+    link.push('.');
+    write_text(link, &key.label);
+}
+
+fn read_setup(setup: &str) -> Option<SetupKey> {
+    let mut parts = setup.splitn(4, '.');
+    let content = parts.next()?;
+    let content = Content::iter().find(|c| c.as_str() == content)?;
+    let chapter = Chapter(parts.next()?.parse().ok()?);
+    let kind = parts.next()?;
+    let kind = match kind.split_at_checked(1)? {
+        ("s", "") => SetupKind::Setup,
+        ("d", door) => SetupKind::Door(door.parse().ok()?),
+        ("n", "") => SetupKind::NamedDoor,
+        ("p", page) => SetupKind::Page(page.parse().ok()?),
+        _ => return None,
+    };
+    let label = read_text(parts.next()?)?;
+    Some(SetupKey {
+        content,
+        chapter,
+        kind,
+        label,
+    })
+}
+
+fn write_text(link: &mut String, text: &str) {
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() {
+            link.push(char::from(byte));
+        } else {
+            let _ = write!(link, "%{byte:02X}");
+        }
+    }
+}
+
+fn read_text(text: &str) -> Option<String> {
+    let mut bytes = Vec::new();
+    let mut rest = text.as_bytes();
+    while let Some((byte, tail)) = rest.split_first() {
+        if *byte == b'%' {
+            let hex = core::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(*byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+const COLORS: [(Color, char); 6] = [
+    (Color::White, 'w'),
+    (Color::Gray, 'g'),
+    (Color::Black, 'b'),
+    (Color::Commander, 'c'),
+    (Color::Special, 's'),
+    (Color::SpecialCommander, 'x'),
+];
+
+const LEVELS: [Level; 4] = [
+    Level::Rookie,
+    Level::Fighter,
+    Level::Veteran,
+    Level::Champion,
+];
+
+fn write_item(
+    link: &mut String,
+    number: Number,
+    color: Color,
+    level: Level,
+    random: Option<RandomSlot>,
+    monster: Option<AnyMonster>,
+    flag: Option<char>,
+) {
+    link.push_str(number.as_str());
+    if random.is_some_and(|random| random.any_color) {
+        link.push('a');
+    } else if let Some((_, c)) = COLORS.iter().find(|(co, _)| *co == color) {
+        link.push(*c);
+    }
+    link.push_str(level.id());
+    if let Some(random) = random.filter(|random| random.max_level > level) {
+        link.push('~');
+        link.push_str(random.max_level.id());
+    }
+    if let Some(AnyMonster::Game(monster)) = monster {
+        write_text(link, monster.name(GameLanguage::En));
+    }
+    if let Some(flag) = flag {
+        link.push(flag);
+    }
+}
+
+type RawItem = (
+    Number,
+    Color,
+    Level,
+    Option<RandomSlot>,
+    Option<Monster>,
+    bool,
+);
+
+fn read_list(list: &str, flag: char) -> Option<Vec<RawItem>> {
+    if list.is_empty() {
+        return Some(Vec::new());
+    }
+    list.split('.').map(|item| read_item(item, flag)).collect()
+}
+
+fn read_item(item: &str, flag: char) -> Option<RawItem> {
+    let (item, flagged) = match item.strip_suffix(flag) {
+        Some(item) => (item, true),
+        None => (item, false),
+    };
+    let number = read_number(item.get(0..1)?)?;
+    let color = item.get(1..2)?.chars().next()?;
+    let any_color = color == 'a';
+    let color = if any_color {
+        Color::White
+    } else {
+        COLORS.iter().find(|(_, c)| *c == color)?.0
+    };
+    let read_level = |level| LEVELS.into_iter().find(|l| l.id() == level);
+    let level = read_level(item.get(2..4)?)?;
+    let (max_level, rest) = match item.get(4..)?.strip_prefix('~') {
+        Some(rest) => (read_level(rest.get(0..2)?)?, rest.get(2..)?),
+        None => (level, item.get(4..)?),
+    };
+    let random = (any_color || max_level > level).then_some(RandomSlot {
+        any_color,
+        max_level,
+    });
+    let monster = match rest {
+        "" => None,
+        monster => {
+            let name = read_text(monster)?;
+            Some(Monster::iter().find(|monster| monster.name(GameLanguage::En) == name)?)
+        }
+    };
+    Some((number, color, level, random, monster, flagged))
+}
+
+fn read_number(number: &str) -> Option<Number> {
+    Number::try_from(number.parse().ok()?)
+}